@@ -1,6 +1,19 @@
 use stellar::Stellar;
 
+// Calls are evaluated recursively, so scripts need more stack than the main
+// thread gets to reach the interpreter's call depth limit.
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
 fn main() {
+    std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(run)
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+fn run() {
     let mut args = std::env::args();
     let mut properties = stellar::lang::InterpreterProperties::default();
 
@@ -14,7 +27,10 @@ fn main() {
             properties.mode = stellar::lang::InterpreterMode::Script;
             let mut stellar = Stellar::new(properties);
             let path = args.nth(1).unwrap();
-            stellar.run_file(path);
+
+            if !stellar.run_file(path) {
+                std::process::exit(1);
+            }
         }
         _ => stellar::print_usage(),
     }
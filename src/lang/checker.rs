@@ -0,0 +1,654 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    ArmBody, BinaryOp, Expr, Import, LogicalOp, MatchArm, Signature, Stmt, Type, TypeAnnotation,
+    UnaryOp,
+};
+
+#[derive(Debug, Clone)]
+struct Binding {
+    ty: Type,
+    annotated: bool,
+    signature: Option<Signature>,
+    declaration: Option<usize>,
+}
+
+impl Binding {
+    fn any() -> Self {
+        Self {
+            ty: Type::Any,
+            annotated: false,
+            signature: None,
+            declaration: None,
+        }
+    }
+}
+
+// Walks the tree before it is interpreted and reports type mismatches that are
+// known statically. Anything the checker can't infer is `any` and left to the runtime.
+#[derive(Debug)]
+pub struct Checker {
+    scopes: Vec<HashMap<String, Binding>>,
+    returns: Vec<Option<TypeAnnotation>>,
+    structs: HashSet<String>,
+    // Declarations are numbered in walk order, which is the same for both passes of `check`
+    declarations: usize,
+    reassigned: HashSet<usize>,
+    silent: bool,
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            returns: Vec::new(),
+            structs: HashSet::new(),
+            declarations: 0,
+            reassigned: HashSet::new(),
+            silent: false,
+        }
+    }
+
+    pub fn check(&mut self, statements: &[Stmt]) {
+        // Struct names can be used in annotations before their declaration
+        self.collect_types(statements);
+
+        // A reassignment can run before a use that comes earlier in the source, in a loop or
+        // a function, so a silent first pass finds the declarations that are reassigned
+        let scopes = self.scopes.clone();
+        self.silent = true;
+        self.check_statements(statements);
+        self.silent = false;
+        self.scopes = scopes;
+
+        self.check_statements(statements);
+        self.reassigned.clear();
+    }
+
+    fn check_statements(&mut self, statements: &[Stmt]) {
+        self.declarations = 0;
+        for statement in statements.iter() {
+            self.check_statement(statement);
+        }
+    }
+
+    fn collect_types(&mut self, statements: &[Stmt]) {
+        for statement in statements.iter() {
+            match statement {
+                Stmt::Struct(name, _, methods) => {
+                    self.structs.insert(name.clone());
+                    self.collect_types(methods);
+                }
+                Stmt::Enum(name, _) => {
+                    self.structs.insert(name.clone());
+                }
+                Stmt::Import(_, Import::Names(names)) => {
+                    self.structs
+                        .extend(names.iter().map(|(_, alias)| alias.clone()));
+                }
+                Stmt::Function(_, _, body, _) | Stmt::Block(body) => self.collect_types(body),
+                Stmt::If(_, then_branch, else_branch) => {
+                    self.collect_types(std::slice::from_ref(then_branch));
+                    if let Some(else_branch) = else_branch {
+                        self.collect_types(std::slice::from_ref(else_branch));
+                    }
+                }
+                Stmt::While(_, body, ..) | Stmt::Loop(body, _) | Stmt::ForIn(_, _, body, _) => {
+                    self.collect_types(std::slice::from_ref(body));
+                }
+                Stmt::Try(body, handler, finalizer) => {
+                    self.collect_types(body);
+                    if let Some((_, statements)) = handler {
+                        self.collect_types(statements);
+                    }
+                    if let Some(statements) = finalizer {
+                        self.collect_types(statements);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn check_statement(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expr(expr) | Stmt::Print(expr) => {
+                self.infer(expr);
+            }
+            Stmt::If(condition, then_branch, else_branch) => {
+                self.infer(condition);
+                self.check_scoped(std::slice::from_ref(then_branch), Vec::new());
+                if let Some(else_branch) = else_branch {
+                    self.check_scoped(std::slice::from_ref(else_branch), Vec::new());
+                }
+            }
+            Stmt::Let(name, initializer, annotation) => {
+                let declaration = self.declarations;
+                self.declarations += 1;
+
+                let mut found = initializer.as_ref().map(|expr| self.infer(expr));
+
+                // Only the first value of a reassigned variable is known
+                if annotation.is_none() && self.reassigned.contains(&declaration) {
+                    found = found.map(|_| Type::Any);
+                }
+
+                self.define_checked(name, found, annotation);
+                if let Some(binding) = self.scope().get_mut(name) {
+                    binding.declaration = Some(declaration);
+                }
+                self.remember_signature(name, initializer.as_deref());
+            }
+            Stmt::Const(name, initializer, annotation) => {
+                let found = self.infer(initializer);
+                self.define_checked(name, Some(found), annotation);
+                self.remember_signature(name, Some(initializer));
+            }
+            Stmt::Destructure(pattern, value) => {
+                self.infer(value);
+                for name in pattern.bindings() {
+                    self.define(name, Binding::any());
+                }
+            }
+            Stmt::Block(statements) => self.check_scoped(statements, Vec::new()),
+            Stmt::Function(name, params, body, signature) => {
+                self.define(
+                    name.clone(),
+                    Binding {
+                        ty: Type::Function,
+                        annotated: false,
+                        signature: Some(signature.clone()),
+                        declaration: None,
+                    },
+                );
+                self.check_function(params, body, signature);
+            }
+            Stmt::Return(value, line) => {
+                let found = match value {
+                    Some(value) => self.infer(value),
+                    None => Type::Null,
+                };
+
+                if let Some(Some(expected)) = self.returns.last().cloned() {
+                    if !expected.ty.accepts(&found) {
+                        self.error(
+                            *line,
+                            format!("expected return type '{}', found '{found}'", expected.ty),
+                        );
+                    }
+                }
+            }
+            Stmt::Struct(name, _, methods) => {
+                self.define(name.clone(), Binding::any());
+
+                self.scopes.push(HashMap::new());
+                self.define("self".to_string(), Binding::any());
+                for method in methods.iter() {
+                    if let Stmt::Function(_, params, body, signature) = method {
+                        self.check_function(params, body, signature);
+                    }
+                }
+                self.scopes.pop();
+            }
+            Stmt::Enum(name, _) => self.define(name.clone(), Binding::any()),
+            Stmt::While(condition, body, increment, _) => {
+                self.infer(condition);
+                self.check_scoped(std::slice::from_ref(body), Vec::new());
+                if let Some(increment) = increment {
+                    self.infer(increment);
+                }
+            }
+            Stmt::Loop(body, _) => self.check_scoped(std::slice::from_ref(body), Vec::new()),
+            Stmt::ForIn(pattern, iterable, body, _) => {
+                self.infer(iterable);
+                self.check_scoped(std::slice::from_ref(body), pattern.bindings());
+            }
+            Stmt::Break(_, value) => {
+                if let Some(value) = value {
+                    self.infer(value);
+                }
+            }
+            Stmt::Continue(_) => (),
+            Stmt::Match(value, arms) => {
+                self.infer(value);
+                self.check_arms(arms);
+            }
+            Stmt::Try(body, handler, finalizer) => {
+                self.check_scoped(body, Vec::new());
+                if let Some((name, statements)) = handler {
+                    self.check_scoped(statements, vec![name.clone()]);
+                }
+                if let Some(statements) = finalizer {
+                    self.check_scoped(statements, Vec::new());
+                }
+            }
+            Stmt::Throw(value) => {
+                self.infer(value);
+            }
+            Stmt::Import(_, import) => match import {
+                Import::Module(name) => self.define(
+                    name.clone(),
+                    Binding {
+                        ty: Type::Module,
+                        annotated: false,
+                        signature: None,
+                        declaration: None,
+                    },
+                ),
+                Import::Names(names) => {
+                    for (_, alias) in names.iter() {
+                        self.define(alias.clone(), Binding::any());
+                    }
+                }
+            },
+        }
+    }
+
+    fn check_scoped(&mut self, statements: &[Stmt], bindings: Vec<String>) {
+        self.scopes.push(HashMap::new());
+        for name in bindings {
+            self.define(name, Binding::any());
+        }
+
+        for statement in statements.iter() {
+            self.check_statement(statement);
+        }
+
+        self.scopes.pop();
+    }
+
+    fn check_function(&mut self, params: &[String], body: &[Stmt], signature: &Signature) {
+        self.scopes.push(HashMap::new());
+        for (name, annotation) in params.iter().zip(signature.params.iter()) {
+            let binding = match annotation {
+                Some(annotation) => {
+                    self.check_known(annotation);
+                    Binding {
+                        ty: annotation.ty.clone(),
+                        annotated: true,
+                        signature: None,
+                        declaration: None,
+                    }
+                }
+                None => Binding::any(),
+            };
+
+            self.define(name.clone(), binding);
+        }
+
+        if let Some(ret) = &signature.ret {
+            self.check_known(ret);
+        }
+
+        self.returns.push(signature.ret.clone());
+        for statement in body.iter() {
+            self.check_statement(statement);
+        }
+        self.returns.pop();
+
+        self.scopes.pop();
+    }
+
+    fn check_arms(&mut self, arms: &[MatchArm]) {
+        for arm in arms.iter() {
+            self.scopes.push(HashMap::new());
+            for name in arm.pattern.bindings() {
+                self.define(name, Binding::any());
+            }
+
+            if let Some(guard) = &arm.guard {
+                self.infer(guard);
+            }
+
+            match &arm.body {
+                ArmBody::Expr(expr) => {
+                    self.infer(expr);
+                }
+                ArmBody::Block(statements) => self.check_scoped(statements, Vec::new()),
+            }
+
+            self.scopes.pop();
+        }
+    }
+
+    fn infer(&mut self, expr: &Expr) -> Type {
+        match expr {
+            Expr::Binary(lhs, op, rhs, line) => {
+                let left = self.infer(lhs);
+                let right = self.infer(rhs);
+
+                match self.binary_type(&left, *op, &right) {
+                    Some(ty) => ty,
+                    None => {
+                        self.error(
+                            *line,
+                            format!("'{op:?}' not supported for types '{left}' and '{right}'"),
+                        );
+                        Type::Any
+                    }
+                }
+            }
+            Expr::Grouping(expr) => self.infer(expr),
+            Expr::Logical(lhs, LogicalOp::And | LogicalOp::Or, rhs) => {
+                self.infer(lhs);
+                self.infer(rhs);
+                Type::Bool
+            }
+            Expr::Unary(op, expr) => {
+                let ty = self.infer(expr);
+                match (op, ty) {
+                    (UnaryOp::Bang, _) => Type::Bool,
+                    (UnaryOp::Minus, ty) if ty.is_numeric() => ty,
+                    (UnaryOp::BitNot, Type::Int | Type::Number) => Type::Int,
+                    _ => Type::Any,
+                }
+            }
+            Expr::Literal(literal) => Type::of(literal),
+            Expr::Variable(name) => self.lookup(name).map(|b| b.ty).unwrap_or(Type::Any),
+            Expr::Assign(name, value, line) => {
+                let found = self.infer(value);
+
+                if let Some(binding) = self.lookup_mut(name) {
+                    // The variable may now hold any function, so its signature can't be trusted
+                    binding.signature = None;
+                    let declaration = binding.declaration;
+
+                    if !binding.annotated {
+                        binding.ty = Type::Any;
+                    } else if !binding.ty.accepts(&found) {
+                        let expected = binding.ty.clone();
+                        self.error(
+                            *line,
+                            format!("expected '{expected}' for '{name}', found '{found}'"),
+                        );
+                    }
+
+                    self.reassigned.extend(declaration);
+                }
+
+                found
+            }
+            Expr::CompoundAssign(target, _, value) => {
+                self.infer(target);
+                self.infer(value);
+
+                if let Expr::Variable(name) = target.as_ref() {
+                    if let Some(binding) = self.lookup_mut(name) {
+                        if !binding.annotated {
+                            binding.ty = Type::Any;
+                        }
+
+                        let declaration = binding.declaration;
+                        self.reassigned.extend(declaration);
+                    }
+                }
+
+                Type::Any
+            }
+            Expr::Call(callee, arguments, line) => {
+                let found: Vec<Type> = arguments.iter().map(|arg| self.infer(arg)).collect();
+
+                let signature = match callee.as_ref() {
+                    Expr::Variable(name) => self.lookup(name).and_then(|b| b.signature),
+                    _ => {
+                        self.infer(callee);
+                        None
+                    }
+                };
+
+                match signature {
+                    Some(signature) => {
+                        self.check_call(&signature, &found, *line);
+                        signature.ret.map(|ret| ret.ty).unwrap_or(Type::Any)
+                    }
+                    None => Type::Any,
+                }
+            }
+            Expr::Get(object, _) => {
+                self.infer(object);
+                Type::Any
+            }
+            Expr::Set(object, _, value) => {
+                self.infer(object);
+                self.infer(value)
+            }
+            Expr::Construct(name, fields) => {
+                for (_, value) in fields.iter() {
+                    self.infer(value);
+                }
+                Type::Named(name.clone())
+            }
+            Expr::List(elements) => {
+                for element in elements.iter() {
+                    self.infer(element);
+                }
+                Type::List
+            }
+            Expr::Index(object, index) => {
+                self.infer(object);
+                self.infer(index);
+                Type::Any
+            }
+            Expr::SetIndex(object, index, value) => {
+                self.infer(object);
+                self.infer(index);
+                self.infer(value)
+            }
+            Expr::Map(entries) => {
+                for (key, value) in entries.iter() {
+                    self.infer(key);
+                    self.infer(value);
+                }
+                Type::Map
+            }
+            Expr::Loop(body, _) => {
+                self.check_scoped(std::slice::from_ref(body), Vec::new());
+                Type::Any
+            }
+            Expr::Tuple(elements) => {
+                for element in elements.iter() {
+                    self.infer(element);
+                }
+                Type::Tuple
+            }
+            Expr::Match(value, arms) => {
+                self.infer(value);
+                self.check_arms(arms);
+                Type::Any
+            }
+            Expr::Interpolation(parts) => {
+                for part in parts.iter() {
+                    self.infer(part);
+                }
+                Type::String
+            }
+            Expr::Range(start, end, _) => {
+                self.infer(start);
+                self.infer(end);
+                Type::Range
+            }
+            Expr::Lambda(params, body, signature) => {
+                self.check_function(params, body, signature);
+                Type::Function
+            }
+        }
+    }
+
+    fn check_call(&mut self, signature: &Signature, arguments: &[Type], line: u32) {
+        if signature.params.len() != arguments.len() {
+            self.error(
+                line,
+                format!(
+                    "expected {} arguments but got {}",
+                    signature.params.len(),
+                    arguments.len()
+                ),
+            );
+            return;
+        }
+
+        for (i, (param, found)) in signature.params.iter().zip(arguments.iter()).enumerate() {
+            if let Some(expected) = param {
+                if !expected.ty.accepts(found) {
+                    self.error(
+                        line,
+                        format!(
+                            "expected '{}' for argument {}, found '{found}'",
+                            expected.ty,
+                            i + 1
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    // Mirrors the operand rules of `Interpreter::binary_operation`, `None` means the operation
+    // is guaranteed to fail at runtime
+    fn binary_type(&self, left: &Type, op: BinaryOp, right: &Type) -> Option<Type> {
+        let comparison = matches!(
+            op,
+            BinaryOp::Equal
+                | BinaryOp::NotEqual
+                | BinaryOp::Gt
+                | BinaryOp::Gte
+                | BinaryOp::Lt
+                | BinaryOp::Lte
+        );
+
+        if *left == Type::Any || *right == Type::Any {
+            return Some(if comparison { Type::Bool } else { Type::Any });
+        }
+
+        if left.is_numeric() && right.is_numeric() {
+            if op.is_bitwise() {
+                return match (left, right) {
+                    (Type::Float, _) | (_, Type::Float) => None,
+                    _ => Some(Type::Int),
+                };
+            }
+
+            return Some(match (left, right) {
+                _ if comparison => Type::Bool,
+                (Type::Float, _) | (_, Type::Float) => Type::Float,
+                // A negative exponent turns an int power into a float
+                (Type::Int, Type::Int) if !matches!(op, BinaryOp::Pow) => Type::Int,
+                _ => Type::Number,
+            });
+        }
+
+        let equality = matches!(op, BinaryOp::Equal | BinaryOp::NotEqual);
+        match (left, right) {
+            (Type::String, Type::String | Type::Char | Type::Int | Type::Float | Type::Number)
+                if matches!(op, BinaryOp::Add) =>
+            {
+                Some(Type::String)
+            }
+            (
+                Type::String
+                | Type::Bool
+                | Type::Char
+                | Type::List
+                | Type::Tuple
+                | Type::Range
+                | Type::Map,
+                _,
+            ) if equality && left == right => Some(Type::Bool),
+            _ => None,
+        }
+    }
+
+    fn define_checked(
+        &mut self,
+        name: &str,
+        found: Option<Type>,
+        annotation: &Option<TypeAnnotation>,
+    ) {
+        let binding = match annotation {
+            Some(annotation) => {
+                let known = self.check_known(annotation);
+
+                if let (true, Some(found)) = (known, &found) {
+                    if !annotation.ty.accepts(found) {
+                        self.error(
+                            annotation.line,
+                            format!("expected '{}' for '{name}', found '{found}'", annotation.ty),
+                        );
+                    }
+                }
+
+                Binding {
+                    ty: annotation.ty.clone(),
+                    annotated: true,
+                    signature: None,
+                    declaration: None,
+                }
+            }
+            None => Binding {
+                ty: found.unwrap_or(Type::Any),
+                annotated: false,
+                signature: None,
+                declaration: None,
+            },
+        };
+
+        self.define(name.to_string(), binding);
+    }
+
+    fn remember_signature(&mut self, name: &String, initializer: Option<&Expr>) {
+        if let Some(Expr::Lambda(_, _, signature)) = initializer {
+            if let Some(binding) = self.scope().get_mut(name) {
+                binding.signature = Some(signature.clone());
+            }
+        }
+    }
+
+    fn check_known(&mut self, annotation: &TypeAnnotation) -> bool {
+        if let Type::Named(name) = &annotation.ty {
+            if !self.structs.contains(name) {
+                self.error(annotation.line, format!("unknown type '{name}'"));
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn define(&mut self, name: String, binding: Binding) {
+        self.scope().insert(name, binding);
+    }
+
+    fn scope(&mut self) -> &mut HashMap<String, Binding> {
+        self.scopes.last_mut().unwrap()
+    }
+
+    fn lookup(&self, name: &String) -> Option<Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+    }
+
+    fn lookup_mut(&mut self, name: &String) -> Option<&mut Binding> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+    }
+
+    fn error(&self, line: u32, msg: String) {
+        if self.silent {
+            return;
+        }
+
+        crate::error(line, format!("Type Error: {msg}"));
+        crate::set_error_found(true);
+    }
+}
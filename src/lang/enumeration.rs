@@ -0,0 +1,49 @@
+use std::rc::Rc;
+
+use super::Literal;
+
+#[derive(Debug)]
+pub struct Enum {
+    pub name: String,
+    pub variants: Vec<(String, Vec<String>)>,
+}
+
+impl Enum {
+    pub fn new(name: String, variants: Vec<(String, Vec<String>)>) -> Self {
+        Self { name, variants }
+    }
+
+    pub fn fields(&self, variant: &str) -> Option<&Vec<String>> {
+        self.variants
+            .iter()
+            .find(|(name, _)| name == variant)
+            .map(|(_, fields)| fields)
+    }
+}
+
+#[derive(Debug)]
+pub struct Variant {
+    pub enumeration: Rc<Enum>,
+    pub name: String,
+    pub values: Vec<Literal>,
+}
+
+impl Variant {
+    pub fn new(enumeration: Rc<Enum>, name: String, values: Vec<Literal>) -> Self {
+        Self {
+            enumeration,
+            name,
+            values,
+        }
+    }
+
+    pub fn get(&self, field: &String) -> Option<Literal> {
+        let fields = self.enumeration.fields(&self.name)?;
+        let index = fields.iter().position(|name| name == field)?;
+        self.values.get(index).cloned()
+    }
+
+    pub fn is(&self, enumeration: &Rc<Enum>, name: &str) -> bool {
+        Rc::ptr_eq(&self.enumeration, enumeration) && self.name == name
+    }
+}
@@ -1,40 +1,65 @@
-use std::collections::HashMap;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use super::Literal;
 
-#[derive(Clone)]
+#[derive(Debug)]
 pub struct Environment {
-    enclosing: Option<Box<Environment>>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
     variables: HashMap<String, Option<Literal>>,
+    constants: HashSet<String>,
 }
 
 impl Environment {
     pub fn new() -> Self {
         Self {
-            enclosing: None, 
+            enclosing: None,
             variables: HashMap::new(),
+            constants: HashSet::new(),
         }
     }
 
-    pub fn from(enclosing: Box<Environment>) -> Self {
+    pub fn from(enclosing: Rc<RefCell<Environment>>) -> Self {
         Self {
             enclosing: Some(enclosing),
             variables: HashMap::new(),
+            constants: HashSet::new(),
         }
     }
 
-    pub fn define(&mut self, name: String, value: Option<Literal>) {
+    pub fn define(&mut self, name: String, value: Option<Literal>) -> bool {
+        if self.constants.contains(&name) {
+            return false;
+        }
+
         self.variables.insert(name, value);
+        true
     }
 
-    pub fn assign(&mut self, name: String, value: Option<Literal>) {
+    pub fn define_constant(&mut self, name: String, value: Literal) -> bool {
+        if self.constants.contains(&name) {
+            return false;
+        }
+
+        self.variables.insert(name.clone(), Some(value));
+        self.constants.insert(name);
+        true
+    }
+
+    pub fn assign(&mut self, name: String, value: Option<Literal>) -> bool {
         if self.contains(&name) {
-            self.variables.insert(name.clone(), value.clone());
+            self.variables.insert(name, value);
+            return true;
         }
 
-        if let Some(ref mut env) = self.enclosing {
-            env.assign(name, value);
+        if let Some(env) = &self.enclosing {
+            return env.borrow_mut().assign(name, value);
         }
+
+        false
     }
 
     pub fn get(&self, name: &String) -> Option<Literal> {
@@ -43,7 +68,7 @@ impl Environment {
         }
 
         if let Some(env) = &self.enclosing {
-            return env.get(name);
+            return env.borrow().get(name);
         }
 
         None
@@ -52,4 +77,34 @@ impl Environment {
     pub fn contains(&self, name: &String) -> bool {
         self.variables.contains_key(name)
     }
+
+    pub fn is_defined(&self, name: &String) -> bool {
+        if self.variables.contains_key(name) {
+            return true;
+        }
+
+        if let Some(env) = &self.enclosing {
+            return env.borrow().is_defined(name);
+        }
+
+        false
+    }
+
+    pub fn is_constant(&self, name: &String) -> bool {
+        if self.variables.contains_key(name) {
+            return self.constants.contains(name);
+        }
+
+        if let Some(env) = &self.enclosing {
+            return env.borrow().is_constant(name);
+        }
+
+        false
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
 }
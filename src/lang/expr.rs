@@ -1,6 +1,9 @@
-use std::fmt::Display;
+use std::{cell::RefCell, fmt::Display, rc::Rc};
 
-use super::TokenType;
+use super::{
+    Enum, Function, Instance, Map, MatchArm, Module, NativeMethod, Range, Signature, Stmt, Struct,
+    TokenType, Variant,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum BinaryOp {
@@ -10,6 +13,13 @@ pub enum BinaryOp {
     Sub,
     Mul,
     Div,
+    Mod,
+    Pow,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
     Gt,
     Gte,
     Lt,
@@ -25,6 +35,13 @@ impl BinaryOp {
             TokenType::Minus => BinaryOp::Sub,
             TokenType::Star => BinaryOp::Mul,
             TokenType::Slash => BinaryOp::Div,
+            TokenType::Percent => BinaryOp::Mod,
+            TokenType::StarStar => BinaryOp::Pow,
+            TokenType::Amp => BinaryOp::BitAnd,
+            TokenType::Pipe => BinaryOp::BitOr,
+            TokenType::Caret => BinaryOp::BitXor,
+            TokenType::Shl => BinaryOp::Shl,
+            TokenType::Shr => BinaryOp::Shr,
             TokenType::Gt => BinaryOp::Gt,
             TokenType::Gte => BinaryOp::Gte,
             TokenType::Lt => BinaryOp::Lt,
@@ -32,12 +49,31 @@ impl BinaryOp {
             _ => unreachable!(),
         }
     }
+
+    pub fn from_compound(ty: TokenType) -> Self {
+        match ty {
+            TokenType::PlusEq => BinaryOp::Add,
+            TokenType::MinusEq => BinaryOp::Sub,
+            TokenType::StarEq => BinaryOp::Mul,
+            TokenType::SlashEq => BinaryOp::Div,
+            TokenType::PercentEq => BinaryOp::Mod,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn is_bitwise(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::BitAnd | BinaryOp::BitOr | BinaryOp::BitXor | BinaryOp::Shl | BinaryOp::Shr
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum UnaryOp {
     Bang,
     Minus,
+    BitNot,
 }
 
 impl UnaryOp {
@@ -45,6 +81,7 @@ impl UnaryOp {
         match ty {
             TokenType::Bang => UnaryOp::Bang,
             TokenType::Minus => UnaryOp::Minus,
+            TokenType::Tilde => UnaryOp::BitNot,
             _ => unreachable!(),
         }
     }
@@ -68,40 +105,233 @@ impl LogicalOp {
 
 #[derive(Debug, Clone)]
 pub enum Literal {
+    Int(i64),
     Number(f64),
     String(String),
     Bool(bool),
     Char(char),
+    Function(Rc<Function>),
+    Struct(Rc<Struct>),
+    Instance(Rc<RefCell<Instance>>),
+    Enum(Rc<Enum>),
+    Variant(Rc<Variant>),
+    List(Rc<RefCell<Vec<Literal>>>),
+    Tuple(Rc<Vec<Literal>>),
+    Range(Range),
+    Module(Rc<Module>),
+    Map(Rc<RefCell<Map>>),
+    NativeMethod(Rc<NativeMethod>),
     Null,
 }
 
+impl Literal {
+    fn fmt_nested(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal::String(x) => write!(f, "{x:?}"),
+            Literal::Char(x) => write!(f, "{x:?}"),
+            _ => write!(f, "{self}"),
+        }
+    }
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal::Int(x) => write!(f, "{x}"),
+            Literal::Number(x) => write!(f, "{x:?}"),
+            Literal::String(x) => write!(f, "{x}"),
+            Literal::Bool(x) => write!(f, "{x}"),
+            Literal::Char(x) => write!(f, "{x}"),
+            Literal::Function(x) => write!(f, "<fn {}>", x.name),
+            Literal::NativeMethod(x) => write!(f, "<native method {}>", x.name),
+            Literal::Module(x) => write!(f, "<module {}>", x.name),
+            Literal::Struct(x) => write!(f, "<struct {}>", x.name),
+            Literal::Instance(x) => {
+                let instance = x.borrow();
+                write!(f, "{} {{", instance.structure.name)?;
+                for (i, field) in instance.structure.fields.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{separator}{field}: ")?;
+                    match instance.fields.get(field) {
+                        Some(value) => value.fmt_nested(f)?,
+                        None => write!(f, "null")?,
+                    }
+                }
+                write!(f, " }}")
+            }
+            Literal::Enum(x) => write!(f, "<enum {}>", x.name),
+            Literal::Variant(x) => {
+                write!(f, "{}.{}", x.enumeration.name, x.name)?;
+                if x.values.is_empty() {
+                    return Ok(());
+                }
+
+                write!(f, "(")?;
+                for (i, value) in x.values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    value.fmt_nested(f)?;
+                }
+                write!(f, ")")
+            }
+            Literal::List(x) => {
+                write!(f, "[")?;
+                for (i, element) in x.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element.fmt_nested(f)?;
+                }
+                write!(f, "]")
+            }
+            Literal::Tuple(x) => {
+                write!(f, "(")?;
+                for (i, element) in x.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element.fmt_nested(f)?;
+                }
+                if x.len() == 1 {
+                    write!(f, ",")?;
+                }
+                write!(f, ")")
+            }
+            Literal::Range(x) => write!(f, "{x}"),
+            Literal::Map(x) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in x.borrow().entries().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    key.fmt_nested(f)?;
+                    write!(f, ": ")?;
+                    value.fmt_nested(f)?;
+                }
+                write!(f, "}}")
+            }
+            Literal::Null => write!(f, "null"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Expr {
-    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>, u32),
     Grouping(Box<Expr>),
     Logical(Box<Expr>, LogicalOp, Box<Expr>),
     Unary(UnaryOp, Box<Expr>),
     Literal(Literal),
     Variable(String),
-    Assign(String, Box<Expr>),
+    Assign(String, Box<Expr>, u32),
+    CompoundAssign(Box<Expr>, BinaryOp, Box<Expr>),
+    Call(Box<Expr>, Vec<Expr>, u32),
+    Get(Box<Expr>, String),
+    Set(Box<Expr>, String, Box<Expr>),
+    Construct(String, Vec<(String, Expr)>),
+    List(Vec<Expr>),
+    Index(Box<Expr>, Box<Expr>),
+    SetIndex(Box<Expr>, Box<Expr>, Box<Expr>),
+    Map(Vec<(Expr, Expr)>),
+    Loop(Box<Stmt>, Option<String>),
+    Tuple(Vec<Expr>),
+    Match(Box<Expr>, Vec<MatchArm>),
+    Interpolation(Vec<Expr>),
+    Range(Box<Expr>, Box<Expr>, bool),
+    Lambda(Vec<String>, Vec<Stmt>, Signature),
 }
 
 impl Display for Expr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Expr::Binary(lhs, op, rhs) => write!(f, "Binary({lhs}, {op:?}, {rhs})"),
+            Expr::Binary(lhs, op, rhs, _) => write!(f, "Binary({lhs}, {op:?}, {rhs})"),
             Expr::Grouping(expr) => write!(f, "Grouping({expr})"),
             Expr::Logical(lhs, op, rhs) => write!(f, "Logical({lhs}, {op:?}, {rhs})"),
             Expr::Unary(op, expr) => write!(f, "Unary({op:?}, {expr})"),
-            Expr::Literal(literal) => match literal {
-                Literal::Number(x) => write!(f, "Literal({x})"),
-                Literal::String(x) => write!(f, "Literal({x})"),
-                Literal::Bool(x) => write!(f, "Literal({x})"),
-                Literal::Char(x) => write!(f, "Literal({x})"),
-                Literal::Null => write!(f, "Literal(Null)"),
-            },
+            Expr::Literal(Literal::Null) => write!(f, "Literal(Null)"),
+            Expr::Literal(literal) => write!(f, "Literal({literal})"),
             Expr::Variable(name) => write!(f, "Variable({name})"),
-            Expr::Assign(name, expr) => write!(f, "Assign({name}, {expr})"),
+            Expr::Assign(name, expr, _) => write!(f, "Assign({name}, {expr})"),
+            Expr::CompoundAssign(target, op, expr) => {
+                write!(f, "CompoundAssign({target}, {op:?}, {expr})")
+            }
+            Expr::Call(callee, args, _) => {
+                write!(f, "Call({callee}")?;
+                for arg in args.iter() {
+                    write!(f, ", {arg}")?;
+                }
+                write!(f, ")")
+            }
+            Expr::Get(object, name) => write!(f, "Get({object}, {name})"),
+            Expr::Set(object, name, value) => write!(f, "Set({object}, {name}, {value})"),
+            Expr::Construct(name, fields) => {
+                write!(f, "Construct({name}")?;
+                for (field, value) in fields.iter() {
+                    write!(f, ", {field}: {value}")?;
+                }
+                write!(f, ")")
+            }
+            Expr::List(elements) => {
+                write!(f, "List(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, ")")
+            }
+            Expr::Index(object, index) => write!(f, "Index({object}, {index})"),
+            Expr::SetIndex(object, index, value) => {
+                write!(f, "SetIndex({object}, {index}, {value})")
+            }
+            Expr::Map(entries) => {
+                write!(f, "Map(")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{key}: {value}")?;
+                }
+                write!(f, ")")
+            }
+            Expr::Tuple(elements) => {
+                write!(f, "Tuple(")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, ")")
+            }
+            Expr::Lambda(params, ..) => write!(f, "Lambda({})", params.join(", ")),
+            Expr::Range(start, end, inclusive) => {
+                let op = if *inclusive { "..=" } else { ".." };
+                write!(f, "Range({start}{op}{end})")
+            }
+            Expr::Interpolation(parts) => {
+                write!(f, "Interpolation(")?;
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{part}")?;
+                }
+                write!(f, ")")
+            }
+            Expr::Match(value, arms) => {
+                write!(f, "Match({value}")?;
+                for arm in arms.iter() {
+                    write!(f, ", {}", arm.pattern)?;
+                }
+                write!(f, ")")
+            }
+            Expr::Loop(_, label) => match label {
+                Some(label) => write!(f, "Loop('{label})"),
+                None => write!(f, "Loop"),
+            },
         }
     }
 }
@@ -0,0 +1,65 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::{Environment, Literal, Stmt};
+
+pub struct Function {
+    pub name: String,
+    pub params: Vec<String>,
+    pub body: Vec<Stmt>,
+    pub closure: Rc<RefCell<Environment>>,
+}
+
+impl Function {
+    pub fn new(
+        name: String,
+        params: Vec<String>,
+        body: Vec<Stmt>,
+        closure: Rc<RefCell<Environment>>,
+    ) -> Self {
+        Self {
+            name,
+            params,
+            body,
+            closure,
+        }
+    }
+
+    pub fn bind(&self, instance: Literal) -> Function {
+        let mut environment = Environment::from(self.closure.clone());
+        environment.define("self".to_string(), Some(instance));
+
+        Function::new(
+            self.name.clone(),
+            self.params.clone(),
+            self.body.clone(),
+            Rc::new(RefCell::new(environment)),
+        )
+    }
+
+    pub fn arity(&self) -> usize {
+        self.params.len()
+    }
+}
+
+impl std::fmt::Debug for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<fn {}>", self.name)
+    }
+}
+
+#[derive(Debug)]
+pub struct NativeMethod {
+    pub receiver: Literal,
+    pub name: String,
+    pub arity: usize,
+}
+
+impl NativeMethod {
+    pub fn new(receiver: Literal, name: String, arity: usize) -> Self {
+        Self {
+            receiver,
+            name,
+            arity,
+        }
+    }
+}
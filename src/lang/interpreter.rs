@@ -1,5 +1,14 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
 use super::{
-    BinaryOp, Environment, ErrorKind, Expr, Literal, LogicalOp, Result, RuntimeError, Stmt, UnaryOp,
+    ArmBody, BinaryOp, Checker, Enum, Environment, ErrorKind, Expr, Function, Import, Instance,
+    Lexer, Literal, LogicalOp, Map, MatchArm, Module, NativeMethod, Parser, Pattern, Range, Result,
+    RuntimeError, Stmt, Struct, UnaryOp, Variant,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -20,72 +29,214 @@ impl Default for InterpreterProperties {
     }
 }
 
+const MAX_CALL_DEPTH: usize = 1000;
+
+enum Flow {
+    Normal,
+    Return(Literal),
+    Break(Option<String>, Literal),
+    Continue(Option<String>),
+}
+
 pub struct Interpreter {
-    environment: Environment,
+    environment: Rc<RefCell<Environment>>,
     properties: InterpreterProperties,
+    directory: PathBuf,
+    modules: HashMap<PathBuf, Rc<Module>>,
+    loading: Vec<PathBuf>,
+    error_struct: Rc<Struct>,
+    depth: usize,
 }
 
 impl Interpreter {
     pub fn new(properties: InterpreterProperties) -> Self {
         Self {
-            environment: Environment::new(),
+            environment: Rc::new(RefCell::new(Environment::new())),
             properties,
+            directory: PathBuf::from("."),
+            modules: HashMap::new(),
+            loading: Vec::new(),
+            error_struct: Rc::new(Struct::new(
+                "Error".to_string(),
+                vec!["kind".to_string(), "what".to_string()],
+                HashMap::new(),
+            )),
+            depth: 0,
+        }
+    }
+
+    pub fn enter_script(&mut self, path: &Path) {
+        if let Ok(path) = std::fs::canonicalize(path) {
+            if let Some(directory) = path.parent() {
+                self.directory = directory.to_path_buf();
+            }
+
+            self.loading = vec![path];
         }
     }
 
-    pub fn interpret(&mut self, statements: &[Stmt]) {
+    pub fn interpret(&mut self, statements: &[Stmt]) -> Result<()> {
         for statement in statements.iter() {
-            self.execute(statement.clone())
+            self.execute(statement.clone())?;
+        }
+
+        Ok(())
+    }
+
+    fn execute(&mut self, stmt: Stmt) -> Result<Flow> {
+        // Expressions can't produce a flow, so a return inside a loop or match
+        // expression unwinds as an error until it reaches its statement
+        match self.execute_statement(stmt) {
+            Err(RuntimeError {
+                kind: ErrorKind::Return,
+                value,
+                ..
+            }) => Ok(Flow::Return(value.unwrap_or(Literal::Null))),
+            flow => flow,
         }
     }
 
-    fn execute(&mut self, stmt: Stmt) {
+    fn execute_statement(&mut self, stmt: Stmt) -> Result<Flow> {
         match stmt {
-            Stmt::Expr(expr) => match self.evaluate(*expr) {
-                Ok(result) => {
-                    if let InterpreterMode::Repl = self.properties.mode {
-                        crate::print_literal(result);
-                    }
+            Stmt::Expr(expr) => {
+                let result = self.evaluate(*expr)?;
+                if let InterpreterMode::Repl = self.properties.mode {
+                    crate::print_literal(result);
                 }
-                Err(err) => println!("Runtime Error: {err}"),
-            },
+
+                Ok(Flow::Normal)
+            }
             Stmt::If(condition, main_branch, else_branch) => {
                 self.visit_if_statement(*condition, *main_branch, else_branch)
             }
-            Stmt::Let(name, initializer) => self.visit_let_statement(name, initializer),
+            Stmt::Let(name, initializer, _) => {
+                self.visit_let_statement(name, initializer)?;
+                Ok(Flow::Normal)
+            }
+            Stmt::Destructure(pattern, initializer) => {
+                self.visit_destructure_statement(pattern, *initializer)?;
+                Ok(Flow::Normal)
+            }
+            Stmt::Const(name, initializer, _) => {
+                self.visit_const_statement(name, *initializer)?;
+                Ok(Flow::Normal)
+            }
             Stmt::Block(statements) => self.visit_block_statement(&statements),
-            Stmt::Print(expr) => self.visit_print_statement(*expr),
+            Stmt::Print(expr) => {
+                self.visit_print_statement(*expr)?;
+                Ok(Flow::Normal)
+            }
+            Stmt::Function(name, params, body, _) => {
+                self.visit_function_statement(name, params, body)?;
+                Ok(Flow::Normal)
+            }
+            Stmt::Return(value, _) => self.visit_return_statement(value),
+            Stmt::Struct(name, fields, methods) => {
+                self.visit_struct_statement(name, fields, methods)?;
+                Ok(Flow::Normal)
+            }
+            Stmt::Enum(name, variants) => {
+                self.visit_enum_statement(name, variants)?;
+                Ok(Flow::Normal)
+            }
+            Stmt::While(condition, body, increment, label) => {
+                self.visit_while_statement(*condition, *body, increment, label)
+            }
+            Stmt::Loop(body, label) => self.visit_loop_statement(*body, label),
+            Stmt::ForIn(pattern, iterable, body, label) => {
+                self.visit_for_in_statement(pattern, *iterable, *body, label)
+            }
+            Stmt::Break(label, value) => self.visit_break_statement(label, value),
+            Stmt::Continue(label) => Ok(Flow::Continue(label)),
+            Stmt::Match(value, arms) => self.visit_match_statement(*value, arms),
+            Stmt::Import(path, import) => {
+                self.visit_import_statement(path, import)?;
+                Ok(Flow::Normal)
+            }
+            Stmt::Try(body, handler, finalizer) => {
+                self.visit_try_statement(body, handler, finalizer)
+            }
+            Stmt::Throw(value) => {
+                let value = self.evaluate(*value)?;
+                Err(RuntimeError::thrown(value))
+            }
         }
     }
 
-    fn execute_block(&mut self, statements: &[Stmt], environment: Environment) {
+    fn execute_block(&mut self, statements: &[Stmt], environment: Environment) -> Result<Flow> {
+        self.execute_in(statements, Rc::new(RefCell::new(environment)))
+    }
+
+    fn execute_in(
+        &mut self,
+        statements: &[Stmt],
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<Flow> {
         let previous = self.environment.clone();
 
         self.environment = environment;
 
+        // The previous environment has to be restored even when an error unwinds the block
+        let mut flow = Ok(Flow::Normal);
         for statement in statements.iter() {
-            self.execute(statement.clone());
+            flow = self.execute(statement.clone());
+
+            if !matches!(flow, Ok(Flow::Normal)) {
+                break;
+            }
         }
 
         self.environment = previous;
+
+        flow
+    }
+
+    fn evaluate_in(
+        &mut self,
+        expr: Expr,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<Literal> {
+        let previous = self.environment.clone();
+
+        self.environment = environment;
+        let result = self.evaluate(expr);
+        self.environment = previous;
+
+        result
     }
 
-    fn visit_block_statement(&mut self, statements: &[Stmt]) {
-        self.execute_block(
-            statements,
-            Environment::from(Box::new(self.environment.clone())),
-        );
+    fn visit_block_statement(&mut self, statements: &[Stmt]) -> Result<Flow> {
+        self.execute_block(statements, Environment::from(self.environment.clone()))
     }
 
     fn evaluate(&mut self, expr: Expr) -> Result<Literal> {
         match expr {
-            Expr::Binary(lhs, op, rhs) => self.visit_binary_expr(*lhs, op, *rhs),
+            Expr::Binary(lhs, op, rhs, _) => self.visit_binary_expr(*lhs, op, *rhs),
             Expr::Grouping(expr) => self.evaluate(*expr),
             Expr::Logical(lhs, op, rhs) => self.visit_logical_expr(*lhs, op, *rhs),
             Expr::Unary(op, expr) => self.visit_unary_expr(op, *expr),
             Expr::Literal(literal) => Ok(literal),
             Expr::Variable(name) => self.visit_variable_expr(name),
-            Expr::Assign(name, value) => self.visit_assign_expr(name, *value),
+            Expr::Assign(name, value, _) => self.visit_assign_expr(name, *value),
+            Expr::CompoundAssign(target, op, value) => {
+                self.visit_compound_assign_expr(*target, op, *value)
+            }
+            Expr::Call(callee, arguments, _) => self.visit_call_expr(*callee, arguments),
+            Expr::Get(object, name) => self.visit_get_expr(*object, name),
+            Expr::Set(object, name, value) => self.visit_set_expr(*object, name, *value),
+            Expr::Construct(name, fields) => self.visit_construct_expr(name, fields),
+            Expr::List(elements) => self.visit_list_expr(elements),
+            Expr::Index(object, index) => self.visit_index_expr(*object, *index),
+            Expr::SetIndex(object, index, value) => {
+                self.visit_set_index_expr(*object, *index, *value)
+            }
+            Expr::Map(entries) => self.visit_map_expr(entries),
+            Expr::Loop(body, _) => self.visit_loop_expr(*body),
+            Expr::Tuple(elements) => self.visit_tuple_expr(elements),
+            Expr::Match(value, arms) => self.visit_match_expr(*value, arms),
+            Expr::Interpolation(parts) => self.visit_interpolation_expr(parts),
+            Expr::Range(start, end, inclusive) => self.visit_range_expr(*start, *end, inclusive),
+            Expr::Lambda(params, body, _) => self.visit_lambda_expr(params, body),
         }
     }
 
@@ -94,209 +245,1526 @@ impl Interpreter {
         condition: Expr,
         main_branch: Stmt,
         else_branch: Option<Box<Stmt>>,
-    ) {
-        match self.evaluate(condition) {
-            Ok(literal) => {
-                if let Ok(res) = self.is_truthy(literal) {
-                    if let Literal::Bool(value) = res {
-                        if value {
-                            self.execute(main_branch);
-                        } else if else_branch.is_some() {
-                            let else_branch = *else_branch.unwrap();
-                            self.execute(else_branch);
-                        }
-                    }
-                }
+    ) -> Result<Flow> {
+        let literal = self.evaluate(condition)?;
+        if let Ok(Literal::Bool(value)) = self.is_truthy(literal) {
+            if value {
+                return self.execute(main_branch);
+            } else if else_branch.is_some() {
+                let else_branch = *else_branch.unwrap();
+                return self.execute(else_branch);
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn visit_while_statement(
+        &mut self,
+        condition: Expr,
+        body: Stmt,
+        increment: Option<Box<Expr>>,
+        label: Option<String>,
+    ) -> Result<Flow> {
+        loop {
+            let literal = self.evaluate(condition.clone())?;
+            if !matches!(self.is_truthy(literal), Ok(Literal::Bool(true))) {
+                break;
+            }
+
+            match self.execute(body.clone())? {
+                Flow::Break(target, _) if Self::targets(&target, &label) => break,
+                Flow::Continue(target) if Self::targets(&target, &label) => (),
+                Flow::Normal => (),
+                flow => return Ok(flow),
+            }
+
+            if let Some(increment) = &increment {
+                self.evaluate(*increment.clone())?;
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn visit_loop_statement(&mut self, body: Stmt, label: Option<String>) -> Result<Flow> {
+        loop {
+            match self.execute(body.clone())? {
+                Flow::Break(target, _) if Self::targets(&target, &label) => break,
+                Flow::Continue(target) if Self::targets(&target, &label) => (),
+                Flow::Normal => (),
+                flow => return Ok(flow),
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn visit_for_in_statement(
+        &mut self,
+        pattern: Pattern,
+        iterable: Expr,
+        body: Stmt,
+        label: Option<String>,
+    ) -> Result<Flow> {
+        let value = self.evaluate(iterable)?;
+        let items = self.iterate(value)?;
+
+        for item in items {
+            let bindings = self.destructure(&pattern, item)?;
+
+            let mut environment = Environment::from(self.environment.clone());
+            for (name, value) in bindings.into_iter() {
+                environment.define(name, Some(value));
+            }
+
+            let environment = Rc::new(RefCell::new(environment));
+            match self.execute_in(std::slice::from_ref(&body), environment)? {
+                Flow::Break(target, _) if Self::targets(&target, &label) => break,
+                Flow::Continue(target) if Self::targets(&target, &label) => (),
+                Flow::Normal => (),
+                flow => return Ok(flow),
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn iterate(&self, value: Literal) -> Result<Box<dyn Iterator<Item = Literal>>> {
+        match value {
+            Literal::Range(range) => Ok(Box::new(range.iter().map(Literal::Int))),
+            Literal::List(list) => Ok(Box::new(list.borrow().clone().into_iter())),
+            Literal::Tuple(elements) => Ok(Box::new(Rc::unwrap_or_clone(elements).into_iter())),
+            Literal::String(string) => {
+                let chars: Vec<char> = string.chars().collect();
+                Ok(Box::new(chars.into_iter().map(Literal::Char)))
+            }
+            Literal::Map(map) => {
+                let entries: Vec<Literal> = map
+                    .borrow()
+                    .entries()
+                    .iter()
+                    .map(|(key, value)| Literal::Tuple(Rc::new(vec![key.clone(), value.clone()])))
+                    .collect();
+                Ok(Box::new(entries.into_iter()))
+            }
+            _ => {
+                let typename = self.typename_from_literal(value);
+                self.runtime_error(
+                    ErrorKind::TypeMismatch,
+                    format!("cannot iterate over type '{typename}'"),
+                )
+            }
+        }
+    }
+
+    fn visit_break_statement(
+        &mut self,
+        label: Option<String>,
+        value: Option<Box<Expr>>,
+    ) -> Result<Flow> {
+        let value = match value {
+            Some(expr) => self.evaluate(*expr)?,
+            None => Literal::Null,
+        };
+
+        Ok(Flow::Break(label, value))
+    }
+
+    fn targets(target: &Option<String>, label: &Option<String>) -> bool {
+        target.is_none() || target == label
+    }
+
+    fn visit_try_statement(
+        &mut self,
+        body: Vec<Stmt>,
+        handler: Option<(String, Vec<Stmt>)>,
+        finalizer: Option<Vec<Stmt>>,
+    ) -> Result<Flow> {
+        let mut result = self.visit_block_statement(&body);
+
+        if let Some((name, statements)) = handler {
+            if let Err(err) = result {
+                let mut environment = Environment::from(self.environment.clone());
+                environment.define(name, Some(self.error_value(err)));
+                result = self.execute_block(&statements, environment);
             }
-            Err(err) => println!("Runtime Error: {err}"),
         }
+
+        // Any jump or error out of the finally block replaces the pending outcome
+        if let Some(statements) = finalizer {
+            let flow = self.visit_block_statement(&statements)?;
+            if !matches!(flow, Flow::Normal) {
+                return Ok(flow);
+            }
+        }
+
+        result
+    }
+
+    fn error_value(&self, err: RuntimeError) -> Literal {
+        if let Some(value) = err.value {
+            return value;
+        }
+
+        let fields = HashMap::from([
+            (
+                "kind".to_string(),
+                Literal::String(format!("{:?}", err.kind)),
+            ),
+            ("what".to_string(), Literal::String(err.what)),
+        ]);
+
+        let instance = Instance::new(self.error_struct.clone(), fields);
+        Literal::Instance(Rc::new(RefCell::new(instance)))
+    }
+
+    fn visit_function_statement(
+        &mut self,
+        name: String,
+        params: Vec<String>,
+        body: Vec<Stmt>,
+    ) -> Result<()> {
+        let function = Function::new(name.clone(), params, body, self.environment.clone());
+        self.define_variable(name, Some(Literal::Function(Rc::new(function))))
+    }
+
+    fn visit_struct_statement(
+        &mut self,
+        name: String,
+        fields: Vec<String>,
+        methods: Vec<Stmt>,
+    ) -> Result<()> {
+        let mut functions = HashMap::new();
+        for method in methods.into_iter() {
+            if let Stmt::Function(method_name, params, body, _) = method {
+                let function =
+                    Function::new(method_name.clone(), params, body, self.environment.clone());
+                functions.insert(method_name, Rc::new(function));
+            }
+        }
+
+        let structure = Struct::new(name.clone(), fields, functions);
+        self.define_variable(name, Some(Literal::Struct(Rc::new(structure))))
+    }
+
+    fn visit_enum_statement(
+        &mut self,
+        name: String,
+        variants: Vec<(String, Vec<String>)>,
+    ) -> Result<()> {
+        let enumeration = Enum::new(name.clone(), variants);
+        self.define_variable(name, Some(Literal::Enum(Rc::new(enumeration))))
+    }
+
+    fn visit_return_statement(&mut self, value: Option<Box<Expr>>) -> Result<Flow> {
+        let value = match value {
+            Some(expr) => self.evaluate(*expr)?,
+            None => Literal::Null,
+        };
+
+        Ok(Flow::Return(value))
     }
 
-    fn visit_let_statement(&mut self, name: String, value: Option<Box<Expr>>) {
+    fn visit_let_statement(&mut self, name: String, value: Option<Box<Expr>>) -> Result<()> {
         let initial_value = match value {
-            Some(expr) => match self.evaluate(*expr) {
-                Ok(literal) => Some(literal),
-                Err(err) => {
-                    println!("Runtime Error: {err}");
-                    None
-                }
-            },
+            Some(expr) => Some(self.evaluate(*expr)?),
             None => None,
         };
 
-        self.define_variable(name, initial_value);
+        self.define_variable(name, initial_value)
     }
 
-    fn visit_print_statement(&mut self, expr: Expr) {
-        match self.evaluate(expr) {
-            Ok(literal) => crate::print_literal(literal),
-            Err(err) => println!("Runtime Error: {err}"),
+    fn visit_destructure_statement(&mut self, pattern: Pattern, value: Expr) -> Result<()> {
+        let literal = self.evaluate(value)?;
+        let bindings = self.destructure(&pattern, literal)?;
+
+        for (name, value) in bindings.into_iter() {
+            self.define_variable(name, Some(value))?;
         }
+
+        Ok(())
     }
 
-    fn visit_binary_expr(&mut self, lhs: Expr, op: BinaryOp, rhs: Expr) -> Result<Literal> {
-        let left = self.evaluate(lhs)?;
-        let right = self.evaluate(rhs)?;
+    fn destructure(&self, pattern: &Pattern, value: Literal) -> Result<Vec<(String, Literal)>> {
+        match self.match_pattern(pattern, &value)? {
+            Some(bindings) => Ok(bindings),
+            None => self.pattern_mismatch(pattern, value),
+        }
+    }
 
-        if let Literal::Number(lvalue) = left {
-            if let Literal::Number(rvalue) = right {
-                let res = match op {
-                    BinaryOp::Equal => Literal::Bool(lvalue == rvalue),
-                    BinaryOp::NotEqual => Literal::Bool(lvalue != rvalue),
-                    BinaryOp::Add => Literal::Number(lvalue + rvalue),
-                    BinaryOp::Sub => Literal::Number(lvalue - rvalue),
-                    BinaryOp::Mul => Literal::Number(lvalue * rvalue),
-                    BinaryOp::Div => {
-                        if rvalue == 0.0 {
-                            return self.runtime_error(
-                                ErrorKind::ZeroDivision,
-                                "cannot divide by zero".to_string(),
-                            );
-                        }
+    fn match_pattern(
+        &self,
+        pattern: &Pattern,
+        value: &Literal,
+    ) -> Result<Option<Vec<(String, Literal)>>> {
+        let mut bindings = Vec::new();
+
+        match pattern {
+            Pattern::Wildcard => (),
+            Pattern::Binding(name) => bindings.push((name.clone(), value.clone())),
+            Pattern::Literal(literal) => {
+                let equal = match (literal, value) {
+                    (Literal::Null, Literal::Null) => true,
+                    _ => self.values_equal(literal, value),
+                };
+
+                if !equal {
+                    return Ok(None);
+                }
+            }
+            Pattern::Range(start, end, inclusive) => {
+                let in_range = match (start, end, value) {
+                    (Literal::Char(start), Literal::Char(end), Literal::Char(x)) => {
+                        start <= x && if *inclusive { x <= end } else { x < end }
+                    }
+                    _ => {
+                        let end_op = if *inclusive {
+                            BinaryOp::Lte
+                        } else {
+                            BinaryOp::Lt
+                        };
 
-                        Literal::Number(lvalue / rvalue)
+                        self.compare(value, BinaryOp::Gte, start)
+                            && self.compare(value, end_op, end)
                     }
-                    BinaryOp::Gt => Literal::Bool(lvalue > rvalue),
-                    BinaryOp::Gte => Literal::Bool(lvalue >= rvalue),
-                    BinaryOp::Lt => Literal::Bool(lvalue < rvalue),
-                    BinaryOp::Lte => Literal::Bool(lvalue <= rvalue),
                 };
 
-                return Ok(res);
+                if !in_range {
+                    return Ok(None);
+                }
             }
-        } else if let Literal::String(lvalue) = left.clone() {
-            if let Literal::String(rvalue) = right.clone() {
-                if let BinaryOp::Equal = op {
-                    let res = Literal::Bool(lvalue == rvalue);
-                    return Ok(res);
-                } else if let BinaryOp::NotEqual = op {
-                    let res = Literal::Bool(lvalue != rvalue);
-                    return Ok(res);
-                } else if let BinaryOp::Add = op {
-                    let res = Literal::String(lvalue + &rvalue);
-                    return Ok(res);
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives.iter() {
+                    if let Some(bindings) = self.match_pattern(alternative, value)? {
+                        return Ok(Some(bindings));
+                    }
                 }
-            } else if let Literal::Number(rvalue) = right {
-                if let BinaryOp::Add = op {
-                    let res = Literal::String(lvalue + &rvalue.to_string());
-                    return Ok(res);
+
+                return Ok(None);
+            }
+            Pattern::Tuple(patterns) => {
+                let Literal::Tuple(elements) = value else {
+                    return Ok(None);
+                };
+
+                if elements.len() != patterns.len() {
+                    return Ok(None);
                 }
-            } else if let Literal::Char(rvalue) = right {
-                if let BinaryOp::Add = op {
-                    let res = Literal::String(lvalue + &rvalue.to_string());
-                    return Ok(res);
+
+                for (pattern, element) in patterns.iter().zip(elements.iter()) {
+                    let Some(found) = self.match_pattern(pattern, element)? else {
+                        return Ok(None);
+                    };
+                    bindings.extend(found);
                 }
             }
-        } else if let Literal::Bool(lvalue) = left {
-            if let Literal::Bool(rvalue) = right {
-                if let BinaryOp::Equal = op {
-                    let res = Literal::Bool(lvalue == rvalue);
-                    return Ok(res);
-                } else if let BinaryOp::NotEqual = op {
-                    let res = Literal::Bool(lvalue != rvalue);
-                    return Ok(res);
+            Pattern::List(patterns, rest) => {
+                let Literal::List(elements) = value else {
+                    return Ok(None);
+                };
+
+                let elements = elements.borrow().clone();
+                if elements.len() < patterns.len()
+                    || (rest.is_none() && elements.len() != patterns.len())
+                {
+                    return Ok(None);
                 }
-            }
-        } else if let Literal::Char(lvalue) = left {
-            if let Literal::Char(rvalue) = right {
-                if let BinaryOp::Equal = op {
-                    let res = Literal::Bool(lvalue == rvalue);
-                    return Ok(res);
-                } else if let BinaryOp::NotEqual = op {
-                    let res = Literal::Bool(lvalue != rvalue);
-                    return Ok(res);
+
+                for (pattern, element) in patterns.iter().zip(elements.iter()) {
+                    let Some(found) = self.match_pattern(pattern, element)? else {
+                        return Ok(None);
+                    };
+                    bindings.extend(found);
+                }
+
+                if let Some(rest) = rest {
+                    let remaining = elements[patterns.len()..].to_vec();
+                    bindings.push((
+                        rest.clone(),
+                        Literal::List(Rc::new(RefCell::new(remaining))),
+                    ));
                 }
             }
-        }
+            Pattern::Variant(path, variant, payload) => {
+                let enumeration = self.resolve_variant(path, variant)?;
 
-        let typename1 = self.typename_from_literal(left);
-        let typename2 = self.typename_from_literal(right);
-        self.runtime_error(
-            ErrorKind::OperatorNotDefined,
-            format!("'{op:?}' not supported for types '{typename1}' and '{typename2}'"),
-        )
-    }
+                let Literal::Variant(value) = value else {
+                    return Ok(None);
+                };
 
-    fn visit_logical_expr(&mut self, lhs: Expr, op: LogicalOp, rhs: Expr) -> Result<Literal> {
-        let left = self.evaluate(lhs)?;
+                if !value.is(&enumeration, variant) {
+                    return Ok(None);
+                }
 
-        match op {
-            LogicalOp::And => {
-                if let Ok(res) = self.is_truthy(left) {
-                    if let Literal::Bool(value) = res {
-                        if !value {
-                            return Ok(Literal::Bool(false));
-                        }
+                if let Some(patterns) = payload {
+                    if patterns.len() != value.values.len() {
+                        return Ok(None);
                     }
-                }
-            }
-            LogicalOp::Or => {
-                if let Ok(res) = self.is_truthy(left) {
-                    if let Literal::Bool(value) = res {
-                        if value {
-                            return Ok(Literal::Bool(true));
-                        }
+
+                    for (pattern, element) in patterns.iter().zip(value.values.iter()) {
+                        let Some(found) = self.match_pattern(pattern, element)? else {
+                            return Ok(None);
+                        };
+                        bindings.extend(found);
                     }
                 }
             }
         }
 
-        let right = self.evaluate(rhs)?;
-        
-        if let Ok(res) = self.is_truthy(right) {
-            if let Literal::Bool(value) = res {
-                return Ok(Literal::Bool(value));
-            }
+        Ok(Some(bindings))
+    }
+
+    // A pattern that names no enum or no variant is an error rather than a
+    // pattern that never matches, e.g. when a parameter shadows the enum
+    fn resolve_variant(&self, path: &[String], variant: &String) -> Result<Rc<Enum>> {
+        let mut value = self.visit_variable_expr(path[0].clone())?;
+
+        for name in path[1..].iter() {
+            let Literal::Module(module) = &value else {
+                let typename = self.typename_from_literal(value);
+                return self.runtime_error(
+                    ErrorKind::TypeMismatch,
+                    format!("cannot access field '{name}' on type '{typename}'"),
+                );
+            };
+
+            let Some(found) = module.get(name) else {
+                return self.runtime_error(
+                    ErrorKind::UndefinedField,
+                    format!("module '{}' has no binding '{name}'", module.name),
+                );
+            };
+
+            value = found;
         }
 
-        unreachable!()
+        let Literal::Enum(enumeration) = value else {
+            return self.runtime_error(
+                ErrorKind::TypeMismatch,
+                format!("'{}' in pattern is not an enum", path.join(".")),
+            );
+        };
+
+        if enumeration.fields(variant).is_none() {
+            return self.runtime_error(
+                ErrorKind::UndefinedField,
+                format!("enum '{}' has no variant '{variant}'", enumeration.name),
+            );
+        }
+
+        Ok(enumeration)
     }
 
-    fn visit_unary_expr(&mut self, op: UnaryOp, expr: Expr) -> Result<Literal> {
-        let value = self.evaluate(expr)?;
+    fn visit_match_statement(&mut self, value: Expr, arms: Vec<MatchArm>) -> Result<Flow> {
+        let value = self.evaluate(value)?;
+        let (index, environment) = self.select_arm(value, &arms)?;
 
-        match op {
-            UnaryOp::Bang => self.is_truthy(value),
-            UnaryOp::Minus => self.negate(value),
+        match &arms[index].body {
+            ArmBody::Block(statements) => self.execute_in(statements, environment),
+            ArmBody::Expr(expr) => {
+                self.evaluate_in(expr.clone(), environment)?;
+                Ok(Flow::Normal)
+            }
         }
     }
 
-    fn visit_variable_expr(&self, name: String) -> Result<Literal> {
-        match self.environment.get(&name) {
-            Some(value) => Ok(value),
-            None => self.runtime_error(
-                ErrorKind::UninitializedAccess,
-                format!(
-                    "variable '{name}' was not initialized, cannot read from unititialized memory"
-                ),
-            ),
+    fn visit_match_expr(&mut self, value: Expr, arms: Vec<MatchArm>) -> Result<Literal> {
+        let value = self.evaluate(value)?;
+        let (index, environment) = self.select_arm(value, &arms)?;
+
+        match &arms[index].body {
+            ArmBody::Block(statements) => match self.execute_in(statements, environment)? {
+                Flow::Return(value) => Err(RuntimeError::returning(value)),
+                // Other jumps out of a match expression are rejected by the parser
+                _ => Ok(Literal::Null),
+            },
+            ArmBody::Expr(expr) => self.evaluate_in(expr.clone(), environment),
         }
     }
 
-    fn visit_assign_expr(&mut self, name: String, value: Expr) -> Result<Literal> {
-        if !self.environment.contains(&name) {
-            return self.runtime_error(ErrorKind::UndefinedVariable, format!("'{name}'"));
-        }
+    fn select_arm(
+        &mut self,
+        value: Literal,
+        arms: &[MatchArm],
+    ) -> Result<(usize, Rc<RefCell<Environment>>)> {
+        for (index, arm) in arms.iter().enumerate() {
+            let Some(bindings) = self.match_pattern(&arm.pattern, &value)? else {
+                continue;
+            };
 
-        match self.evaluate(value) {
-            Ok(literal) => {
-                self.environment.assign(name, Some(literal.clone()));
-                Ok(literal)
+            let mut environment = Environment::from(self.environment.clone());
+            for (name, value) in bindings.into_iter() {
+                environment.define(name, Some(value));
             }
-            Err(err) => Err(err),
+            let environment = Rc::new(RefCell::new(environment));
+
+            if let Some(guard) = &arm.guard {
+                let condition = self.evaluate_in(guard.clone(), environment.clone())?;
+                if let Literal::Bool(false) = self.is_truthy(condition)? {
+                    continue;
+                }
+            }
+
+            return Ok((index, environment));
         }
+
+        self.runtime_error(
+            ErrorKind::PatternMismatch,
+            format!("no match arm for value '{value}'"),
+        )
     }
 
-    fn define_variable(&mut self, name: String, value: Option<Literal>) {
-        self.environment.define(name, value);
+    fn pattern_mismatch<T>(&self, pattern: &Pattern, value: Literal) -> Result<T> {
+        self.runtime_error(
+            ErrorKind::PatternMismatch,
+            format!("cannot destructure '{value}' with pattern '{pattern}'"),
+        )
     }
 
-    fn is_truthy(&self, value: Literal) -> Result<Literal> {
-        let res = match value {
-            Literal::Number(x) => Literal::Bool(x > 0.0),
-            Literal::String(x) => Literal::Bool(!x.is_empty()),
-            Literal::Bool(x) => Literal::Bool(x),
-            Literal::Char(x) => Literal::Bool(x != '0'),
+    fn visit_import_statement(&mut self, path: String, import: Import) -> Result<()> {
+        let module = self.load_module(&path)?;
+
+        match import {
+            Import::Module(alias) => self.define_variable(alias, Some(Literal::Module(module)))?,
+            Import::Names(names) => {
+                for (name, alias) in names.into_iter() {
+                    if !module.has(&name) {
+                        return self.runtime_error(
+                            ErrorKind::ImportError,
+                            format!("module '{}' has no binding '{name}'", module.name),
+                        );
+                    }
+
+                    match module.get(&name) {
+                        Some(value) if module.is_constant(&name) => {
+                            self.define_constant(alias, value)?
+                        }
+                        value => self.define_variable(alias, value)?,
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_module(&mut self, path: &str) -> Result<Rc<Module>> {
+        let mut resolved = self.directory.join(path);
+        if resolved.extension().is_none() {
+            resolved.set_extension("st");
+        }
+
+        let Ok(resolved) = std::fs::canonicalize(&resolved) else {
+            return self.runtime_error(
+                ErrorKind::ImportError,
+                format!("cannot find module '{path}'"),
+            );
+        };
+
+        if let Some(module) = self.modules.get(&resolved) {
+            return Ok(module.clone());
+        }
+
+        if let Some(start) = self.loading.iter().position(|loading| *loading == resolved) {
+            let cycle: Vec<String> = self.loading[start..]
+                .iter()
+                .chain(std::iter::once(&resolved))
+                .map(|path| path.display().to_string())
+                .collect();
+
+            return self.runtime_error(
+                ErrorKind::ImportError,
+                format!("import cycle detected: {}", cycle.join(" -> ")),
+            );
+        }
+
+        let Ok(source) = std::fs::read_to_string(&resolved) else {
+            return self.runtime_error(
+                ErrorKind::ImportError,
+                format!("cannot read module '{path}'"),
+            );
+        };
+
+        let mut lexer = Lexer::new(source);
+        let tokens = lexer.scan_tokens();
+        let statements = Parser::new(tokens).parse();
+
+        if !crate::error_found() {
+            Checker::new().check(&statements);
+        }
+
+        if crate::error_found() {
+            crate::set_error_found(false);
+            return self.runtime_error(
+                ErrorKind::ImportError,
+                format!("module '{path}' contains errors"),
+            );
+        }
+
+        // Modules run in their own global scope, relative to their own directory
+        let environment = Rc::new(RefCell::new(Environment::new()));
+        let directory = resolved.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let previous_environment = std::mem::replace(&mut self.environment, environment.clone());
+        let previous_directory = std::mem::replace(&mut self.directory, directory);
+        let previous_mode = std::mem::replace(&mut self.properties.mode, InterpreterMode::Script);
+        self.loading.push(resolved.clone());
+
+        let result = self.interpret(&statements);
+
+        self.loading.pop();
+        self.properties.mode = previous_mode;
+        self.directory = previous_directory;
+        self.environment = previous_environment;
+
+        result?;
+
+        let name = resolved
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let module = Rc::new(Module::new(name, environment));
+        self.modules.insert(resolved, module.clone());
+
+        Ok(module)
+    }
+
+    fn visit_const_statement(&mut self, name: String, value: Expr) -> Result<()> {
+        let literal = self.evaluate(value)?;
+        self.define_constant(name, literal)
+    }
+
+    fn visit_print_statement(&mut self, expr: Expr) -> Result<()> {
+        let literal = self.evaluate(expr)?;
+        crate::print_literal(literal);
+        Ok(())
+    }
+
+    fn visit_binary_expr(&mut self, lhs: Expr, op: BinaryOp, rhs: Expr) -> Result<Literal> {
+        let left = self.evaluate(lhs)?;
+        let right = self.evaluate(rhs)?;
+
+        self.binary_operation(left, op, right)
+    }
+
+    fn binary_operation(&self, left: Literal, op: BinaryOp, right: Literal) -> Result<Literal> {
+        // Ints stay ints, any float operand promotes the whole operation to float
+        match (&left, &right) {
+            (Literal::Int(lvalue), Literal::Int(rvalue)) => {
+                return self.int_operation(*lvalue, op, *rvalue);
+            }
+            (Literal::Int(..) | Literal::Number(..), Literal::Int(..) | Literal::Number(..))
+                if !op.is_bitwise() =>
+            {
+                return self.float_operation(self.to_float(&left), op, self.to_float(&right));
+            }
+            _ => (),
+        }
+
+        if let Literal::String(lvalue) = left.clone() {
+            if let Literal::String(rvalue) = right.clone() {
+                if let BinaryOp::Equal = op {
+                    let res = Literal::Bool(lvalue == rvalue);
+                    return Ok(res);
+                } else if let BinaryOp::NotEqual = op {
+                    let res = Literal::Bool(lvalue != rvalue);
+                    return Ok(res);
+                } else if let BinaryOp::Add = op {
+                    let res = Literal::String(lvalue + &rvalue);
+                    return Ok(res);
+                }
+            } else if let Literal::Int(rvalue) = right {
+                if let BinaryOp::Add = op {
+                    let res = Literal::String(lvalue + &rvalue.to_string());
+                    return Ok(res);
+                }
+            } else if let Literal::Number(rvalue) = right {
+                if let BinaryOp::Add = op {
+                    let res = Literal::String(lvalue + &rvalue.to_string());
+                    return Ok(res);
+                }
+            } else if let Literal::Char(rvalue) = right {
+                if let BinaryOp::Add = op {
+                    let res = Literal::String(lvalue + &rvalue.to_string());
+                    return Ok(res);
+                }
+            }
+        } else if let Literal::Bool(lvalue) = left {
+            if let Literal::Bool(rvalue) = right {
+                if let BinaryOp::Equal = op {
+                    let res = Literal::Bool(lvalue == rvalue);
+                    return Ok(res);
+                } else if let BinaryOp::NotEqual = op {
+                    let res = Literal::Bool(lvalue != rvalue);
+                    return Ok(res);
+                }
+            }
+        } else if let Literal::Char(lvalue) = left {
+            if let Literal::Char(rvalue) = right {
+                if let BinaryOp::Equal = op {
+                    let res = Literal::Bool(lvalue == rvalue);
+                    return Ok(res);
+                } else if let BinaryOp::NotEqual = op {
+                    let res = Literal::Bool(lvalue != rvalue);
+                    return Ok(res);
+                }
+            }
+        } else if let Literal::List(lvalue) = &left {
+            if let Literal::List(rvalue) = &right {
+                let equal = self.sequences_equal(&lvalue.borrow(), &rvalue.borrow());
+
+                if let BinaryOp::Equal = op {
+                    return Ok(Literal::Bool(equal));
+                } else if let BinaryOp::NotEqual = op {
+                    return Ok(Literal::Bool(!equal));
+                }
+            }
+        } else if let Literal::Tuple(lvalue) = &left {
+            if let Literal::Tuple(rvalue) = &right {
+                let equal = self.sequences_equal(lvalue, rvalue);
+
+                if let BinaryOp::Equal = op {
+                    return Ok(Literal::Bool(equal));
+                } else if let BinaryOp::NotEqual = op {
+                    return Ok(Literal::Bool(!equal));
+                }
+            }
+        } else if let Literal::Range(lvalue) = &left {
+            if let Literal::Range(rvalue) = &right {
+                if let BinaryOp::Equal = op {
+                    return Ok(Literal::Bool(lvalue == rvalue));
+                } else if let BinaryOp::NotEqual = op {
+                    return Ok(Literal::Bool(lvalue != rvalue));
+                }
+            }
+        } else if let Literal::Variant(lvalue) = &left {
+            if let Literal::Variant(rvalue) = &right {
+                let equal = rvalue.is(&lvalue.enumeration, &lvalue.name)
+                    && self.sequences_equal(&lvalue.values, &rvalue.values);
+
+                if let BinaryOp::Equal = op {
+                    return Ok(Literal::Bool(equal));
+                } else if let BinaryOp::NotEqual = op {
+                    return Ok(Literal::Bool(!equal));
+                }
+            }
+        } else if let Literal::Map(lvalue) = &left {
+            if let Literal::Map(rvalue) = &right {
+                let equal = {
+                    let (lvalue, rvalue) = (lvalue.borrow(), rvalue.borrow());
+                    lvalue.len() == rvalue.len()
+                        && lvalue.entries().iter().all(|(key, value)| {
+                            rvalue
+                                .get(key)
+                                .is_some_and(|other| self.values_equal(value, &other))
+                        })
+                };
+
+                if let BinaryOp::Equal = op {
+                    return Ok(Literal::Bool(equal));
+                } else if let BinaryOp::NotEqual = op {
+                    return Ok(Literal::Bool(!equal));
+                }
+            }
+        }
+
+        let typename1 = self.typename_from_literal(left);
+        let typename2 = self.typename_from_literal(right);
+        self.runtime_error(
+            ErrorKind::OperatorNotDefined,
+            format!("'{op:?}' not supported for types '{typename1}' and '{typename2}'"),
+        )
+    }
+
+    fn int_operation(&self, lvalue: i64, op: BinaryOp, rvalue: i64) -> Result<Literal> {
+        let result = match op {
+            BinaryOp::Equal => return Ok(Literal::Bool(lvalue == rvalue)),
+            BinaryOp::NotEqual => return Ok(Literal::Bool(lvalue != rvalue)),
+            BinaryOp::Gt => return Ok(Literal::Bool(lvalue > rvalue)),
+            BinaryOp::Gte => return Ok(Literal::Bool(lvalue >= rvalue)),
+            BinaryOp::Lt => return Ok(Literal::Bool(lvalue < rvalue)),
+            BinaryOp::Lte => return Ok(Literal::Bool(lvalue <= rvalue)),
+            BinaryOp::Add => lvalue.checked_add(rvalue),
+            BinaryOp::Sub => lvalue.checked_sub(rvalue),
+            BinaryOp::Mul => lvalue.checked_mul(rvalue),
+            BinaryOp::Div | BinaryOp::Mod => {
+                if rvalue == 0 {
+                    return self.runtime_error(
+                        ErrorKind::ZeroDivision,
+                        "cannot divide by zero".to_string(),
+                    );
+                }
+
+                if let BinaryOp::Div = op {
+                    lvalue.checked_div(rvalue)
+                } else {
+                    lvalue.checked_rem(rvalue)
+                }
+            }
+            BinaryOp::Pow => {
+                if rvalue < 0 {
+                    return Ok(Literal::Number((lvalue as f64).powf(rvalue as f64)));
+                }
+
+                u32::try_from(rvalue)
+                    .ok()
+                    .and_then(|exponent| lvalue.checked_pow(exponent))
+            }
+            BinaryOp::BitAnd => Some(lvalue & rvalue),
+            BinaryOp::BitOr => Some(lvalue | rvalue),
+            BinaryOp::BitXor => Some(lvalue ^ rvalue),
+            BinaryOp::Shl | BinaryOp::Shr => {
+                if !(0..64).contains(&rvalue) {
+                    return self.runtime_error(
+                        ErrorKind::IntegerOverflow,
+                        format!("cannot shift by {rvalue}, shift amount must be in 0..64"),
+                    );
+                }
+
+                if let BinaryOp::Shl = op {
+                    Some(lvalue << rvalue)
+                } else {
+                    Some(lvalue >> rvalue)
+                }
+            }
+        };
+
+        match result {
+            Some(value) => Ok(Literal::Int(value)),
+            None => self.runtime_error(
+                ErrorKind::IntegerOverflow,
+                format!("'{op:?}' of {lvalue} and {rvalue} overflows an int"),
+            ),
+        }
+    }
+
+    fn float_operation(&self, lvalue: f64, op: BinaryOp, rvalue: f64) -> Result<Literal> {
+        let res = match op {
+            BinaryOp::Equal => Literal::Bool(lvalue == rvalue),
+            BinaryOp::NotEqual => Literal::Bool(lvalue != rvalue),
+            BinaryOp::Add => Literal::Number(lvalue + rvalue),
+            BinaryOp::Sub => Literal::Number(lvalue - rvalue),
+            BinaryOp::Mul => Literal::Number(lvalue * rvalue),
+            BinaryOp::Div | BinaryOp::Mod => {
+                if rvalue == 0.0 {
+                    return self.runtime_error(
+                        ErrorKind::ZeroDivision,
+                        "cannot divide by zero".to_string(),
+                    );
+                }
+
+                if let BinaryOp::Div = op {
+                    Literal::Number(lvalue / rvalue)
+                } else {
+                    Literal::Number(lvalue % rvalue)
+                }
+            }
+            BinaryOp::Pow => Literal::Number(lvalue.powf(rvalue)),
+            BinaryOp::BitAnd
+            | BinaryOp::BitOr
+            | BinaryOp::BitXor
+            | BinaryOp::Shl
+            | BinaryOp::Shr => unreachable!(),
+            BinaryOp::Gt => Literal::Bool(lvalue > rvalue),
+            BinaryOp::Gte => Literal::Bool(lvalue >= rvalue),
+            BinaryOp::Lt => Literal::Bool(lvalue < rvalue),
+            BinaryOp::Lte => Literal::Bool(lvalue <= rvalue),
+        };
+
+        Ok(res)
+    }
+
+    fn to_float(&self, value: &Literal) -> f64 {
+        match value {
+            Literal::Int(x) => *x as f64,
+            Literal::Number(x) => *x,
+            _ => unreachable!(),
+        }
+    }
+
+    fn compare(&self, lhs: &Literal, op: BinaryOp, rhs: &Literal) -> bool {
+        matches!(
+            self.binary_operation(lhs.clone(), op, rhs.clone()),
+            Ok(Literal::Bool(true))
+        )
+    }
+
+    fn values_equal(&self, lhs: &Literal, rhs: &Literal) -> bool {
+        matches!(
+            self.binary_operation(lhs.clone(), BinaryOp::Equal, rhs.clone()),
+            Ok(Literal::Bool(true))
+        )
+    }
+
+    fn sequences_equal(&self, lhs: &[Literal], rhs: &[Literal]) -> bool {
+        lhs.len() == rhs.len()
+            && lhs
+                .iter()
+                .zip(rhs.iter())
+                .all(|(l, r)| self.values_equal(l, r))
+    }
+
+    fn visit_logical_expr(&mut self, lhs: Expr, op: LogicalOp, rhs: Expr) -> Result<Literal> {
+        let left = self.evaluate(lhs)?;
+
+        match op {
+            LogicalOp::And => {
+                if let Ok(Literal::Bool(value)) = self.is_truthy(left) {
+                    if !value {
+                        return Ok(Literal::Bool(false));
+                    }
+                }
+            }
+            LogicalOp::Or => {
+                if let Ok(Literal::Bool(value)) = self.is_truthy(left) {
+                    if value {
+                        return Ok(Literal::Bool(true));
+                    }
+                }
+            }
+        }
+
+        let right = self.evaluate(rhs)?;
+
+        if let Ok(Literal::Bool(value)) = self.is_truthy(right) {
+            return Ok(Literal::Bool(value));
+        }
+
+        unreachable!()
+    }
+
+    fn visit_unary_expr(&mut self, op: UnaryOp, expr: Expr) -> Result<Literal> {
+        let value = self.evaluate(expr)?;
+
+        match op {
+            UnaryOp::Bang => self.is_truthy(value),
+            UnaryOp::Minus => self.negate(value),
+            UnaryOp::BitNot => self.bit_not(value),
+        }
+    }
+
+    fn visit_variable_expr(&self, name: String) -> Result<Literal> {
+        if !self.environment.borrow().is_defined(&name) {
+            return self.runtime_error(ErrorKind::UndefinedVariable, format!("'{name}'"));
+        }
+
+        let value = self.environment.borrow().get(&name);
+        match value {
+            Some(value) => Ok(value),
+            None => self.runtime_error(
+                ErrorKind::UninitializedAccess,
+                format!(
+                    "variable '{name}' was not initialized, cannot read from unititialized memory"
+                ),
+            ),
+        }
+    }
+
+    fn visit_assign_expr(&mut self, name: String, value: Expr) -> Result<Literal> {
+        self.check_assignable(&name)?;
+
+        match self.evaluate(value) {
+            Ok(literal) => {
+                self.environment
+                    .borrow_mut()
+                    .assign(name, Some(literal.clone()));
+                Ok(literal)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn visit_compound_assign_expr(
+        &mut self,
+        target: Expr,
+        op: BinaryOp,
+        value: Expr,
+    ) -> Result<Literal> {
+        match target {
+            Expr::Variable(name) => {
+                self.check_assignable(&name)?;
+
+                let current = self.visit_variable_expr(name.clone())?;
+                let value = self.evaluate(value)?;
+                let result = self.binary_operation(current, op, value)?;
+
+                self.environment
+                    .borrow_mut()
+                    .assign(name, Some(result.clone()));
+                Ok(result)
+            }
+            Expr::Get(object, name) => {
+                let object = self.evaluate(*object)?;
+
+                let Literal::Instance(instance) = &object else {
+                    let typename = self.typename_from_literal(object);
+                    return self.runtime_error(
+                        ErrorKind::TypeMismatch,
+                        format!("cannot set field '{name}' on type '{typename}'"),
+                    );
+                };
+
+                let struct_name = instance.borrow().structure.name.clone();
+                if !instance.borrow().structure.has_field(&name) {
+                    return self.runtime_error(
+                        ErrorKind::UndefinedField,
+                        format!("'{struct_name}' has no field '{name}'"),
+                    );
+                }
+
+                let current = Instance::get(instance, &name).unwrap_or(Literal::Null);
+                let value = self.evaluate(value)?;
+                let result = self.binary_operation(current, op, value)?;
+
+                instance.borrow_mut().set(name, result.clone());
+                Ok(result)
+            }
+            Expr::Index(object, index) => {
+                let object = self.evaluate(*object)?;
+                let index = self.evaluate(*index)?;
+
+                let current = self.index_get(&object, &index)?;
+                let value = self.evaluate(value)?;
+                let result = self.binary_operation(current, op, value)?;
+
+                self.index_set(&object, index, result.clone())?;
+                Ok(result)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn check_assignable(&self, name: &String) -> Result<()> {
+        if !self.environment.borrow().is_defined(name) {
+            return self.runtime_error(ErrorKind::UndefinedVariable, format!("'{name}'"));
+        }
+
+        if self.environment.borrow().is_constant(name) {
+            return self.runtime_error(
+                ErrorKind::ConstantReassignment,
+                format!("cannot assign to constant '{name}'"),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn visit_call_expr(&mut self, callee: Expr, arguments: Vec<Expr>) -> Result<Literal> {
+        let callee = self.evaluate(callee)?;
+
+        let mut values = Vec::new();
+        for argument in arguments.into_iter() {
+            values.push(self.evaluate(argument)?);
+        }
+
+        match callee {
+            Literal::Function(function) => self.call_function(function, values),
+            Literal::NativeMethod(method) => self.call_native_method(method, values),
+            _ => {
+                let typename = self.typename_from_literal(callee);
+                self.runtime_error(
+                    ErrorKind::TypeMismatch,
+                    format!("type '{typename}' is not callable"),
+                )
+            }
+        }
+    }
+
+    fn visit_get_expr(&mut self, object: Expr, name: String) -> Result<Literal> {
+        let object = self.evaluate(object)?;
+
+        if let Literal::Instance(instance) = &object {
+            if let Some(value) = Instance::get(instance, &name) {
+                return Ok(value);
+            }
+
+            let struct_name = instance.borrow().structure.name.clone();
+            return self.runtime_error(
+                ErrorKind::UndefinedField,
+                format!("'{struct_name}' has no field or method '{name}'"),
+            );
+        }
+
+        if let Literal::Enum(enumeration) = &object {
+            let Some(fields) = enumeration.fields(&name) else {
+                return self.runtime_error(
+                    ErrorKind::UndefinedField,
+                    format!("enum '{}' has no variant '{name}'", enumeration.name),
+                );
+            };
+
+            // Variants without a payload are values, the others are constructors
+            if fields.is_empty() {
+                let variant = Variant::new(enumeration.clone(), name, Vec::new());
+                return Ok(Literal::Variant(Rc::new(variant)));
+            }
+
+            let constructor = NativeMethod::new(object.clone(), name, fields.len());
+            return Ok(Literal::NativeMethod(Rc::new(constructor)));
+        }
+
+        if let Literal::Variant(variant) = &object {
+            if name == "variant" {
+                return Ok(Literal::String(variant.name.clone()));
+            } else if let Some(value) = variant.get(&name) {
+                return Ok(value);
+            }
+
+            return self.runtime_error(
+                ErrorKind::UndefinedField,
+                format!(
+                    "'{}.{}' has no field '{name}'",
+                    variant.enumeration.name, variant.name
+                ),
+            );
+        }
+
+        if let Literal::Map(..) = &object {
+            if let Some(method) = self.map_method(object.clone(), &name) {
+                return Ok(method);
+            }
+
+            return self.runtime_error(
+                ErrorKind::UndefinedField,
+                format!("'map' has no method '{name}'"),
+            );
+        }
+
+        if let Literal::Module(module) = &object {
+            if module.has(&name) {
+                return match module.get(&name) {
+                    Some(value) => Ok(value),
+                    None => self.runtime_error(
+                        ErrorKind::UninitializedAccess,
+                        format!("'{}.{name}' was not initialized", module.name),
+                    ),
+                };
+            }
+
+            return self.runtime_error(
+                ErrorKind::UndefinedField,
+                format!("module '{}' has no binding '{name}'", module.name),
+            );
+        }
+
+        if let Literal::Range(..) = &object {
+            if let Some(method) = self.range_method(object.clone(), &name) {
+                return Ok(method);
+            }
+
+            return self.runtime_error(
+                ErrorKind::UndefinedField,
+                format!("'range' has no method '{name}'"),
+            );
+        }
+
+        let typename = self.typename_from_literal(object);
+        self.runtime_error(
+            ErrorKind::TypeMismatch,
+            format!("cannot access field '{name}' on type '{typename}'"),
+        )
+    }
+
+    fn visit_set_expr(&mut self, object: Expr, name: String, value: Expr) -> Result<Literal> {
+        let object = self.evaluate(object)?;
+
+        if let Literal::Instance(instance) = &object {
+            let value = self.evaluate(value)?;
+
+            if instance.borrow_mut().set(name.clone(), value.clone()) {
+                return Ok(value);
+            }
+
+            let struct_name = instance.borrow().structure.name.clone();
+            return self.runtime_error(
+                ErrorKind::UndefinedField,
+                format!("'{struct_name}' has no field '{name}'"),
+            );
+        }
+
+        let typename = self.typename_from_literal(object);
+        self.runtime_error(
+            ErrorKind::TypeMismatch,
+            format!("cannot set field '{name}' on type '{typename}'"),
+        )
+    }
+
+    fn visit_construct_expr(
+        &mut self,
+        name: String,
+        fields: Vec<(String, Expr)>,
+    ) -> Result<Literal> {
+        let structure = match self.visit_variable_expr(name.clone())? {
+            Literal::Struct(structure) => structure,
+            other => {
+                let typename = self.typename_from_literal(other);
+                return self.runtime_error(
+                    ErrorKind::TypeMismatch,
+                    format!("'{name}' is of type '{typename}', not a struct"),
+                );
+            }
+        };
+
+        let mut values = HashMap::new();
+        for field in structure.fields.iter() {
+            values.insert(field.clone(), Literal::Null);
+        }
+
+        for (field, value) in fields.into_iter() {
+            if !structure.has_field(&field) {
+                return self.runtime_error(
+                    ErrorKind::UndefinedField,
+                    format!("'{name}' has no field '{field}'"),
+                );
+            }
+
+            let value = self.evaluate(value)?;
+            values.insert(field, value);
+        }
+
+        let instance = Instance::new(structure, values);
+        Ok(Literal::Instance(Rc::new(RefCell::new(instance))))
+    }
+
+    fn visit_loop_expr(&mut self, body: Stmt) -> Result<Literal> {
+        // The parser rejects any break or continue that would escape a loop
+        // expression, so every break that reaches this point targets it
+        loop {
+            match self.execute(body.clone())? {
+                Flow::Break(_, value) => return Ok(value),
+                Flow::Return(value) => return Err(RuntimeError::returning(value)),
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_list_expr(&mut self, elements: Vec<Expr>) -> Result<Literal> {
+        let mut values = Vec::new();
+        for element in elements.into_iter() {
+            values.push(self.evaluate(element)?);
+        }
+
+        Ok(Literal::List(Rc::new(RefCell::new(values))))
+    }
+
+    fn visit_index_expr(&mut self, object: Expr, index: Expr) -> Result<Literal> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+
+        self.index_get(&object, &index)
+    }
+
+    fn visit_set_index_expr(&mut self, object: Expr, index: Expr, value: Expr) -> Result<Literal> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+
+        // Validate the target before evaluating the value, maps only need a valid key
+        if let Literal::List(list) = &object {
+            self.list_index(list.borrow().len(), &index)?;
+        } else if let Literal::Tuple(..) = &object {
+            return self.runtime_error(ErrorKind::TypeMismatch, "tuples are immutable".to_string());
+        } else {
+            self.map_key(&object, &index)?;
+        }
+
+        let value = self.evaluate(value)?;
+        self.index_set(&object, index, value.clone())?;
+        Ok(value)
+    }
+
+    fn visit_tuple_expr(&mut self, elements: Vec<Expr>) -> Result<Literal> {
+        let mut values = Vec::new();
+        for element in elements.into_iter() {
+            values.push(self.evaluate(element)?);
+        }
+
+        Ok(Literal::Tuple(Rc::new(values)))
+    }
+
+    fn visit_lambda_expr(&mut self, params: Vec<String>, body: Vec<Stmt>) -> Result<Literal> {
+        let function = Function::new("lambda".to_string(), params, body, self.environment.clone());
+        Ok(Literal::Function(Rc::new(function)))
+    }
+
+    fn visit_range_expr(&mut self, start: Expr, end: Expr, inclusive: bool) -> Result<Literal> {
+        let start = self.evaluate(start)?;
+        let end = self.evaluate(end)?;
+
+        let (Literal::Int(start), Literal::Int(end)) = (&start, &end) else {
+            let typename1 = self.typename_from_literal(start);
+            let typename2 = self.typename_from_literal(end);
+            return self.runtime_error(
+                ErrorKind::TypeMismatch,
+                format!("range bounds must be ints, found '{typename1}' and '{typename2}'"),
+            );
+        };
+
+        Ok(Literal::Range(Range::new(*start, *end, inclusive)))
+    }
+
+    fn visit_interpolation_expr(&mut self, parts: Vec<Expr>) -> Result<Literal> {
+        let mut string = String::new();
+        for part in parts.into_iter() {
+            string.push_str(&self.evaluate(part)?.to_string());
+        }
+
+        Ok(Literal::String(string))
+    }
+
+    fn visit_map_expr(&mut self, entries: Vec<(Expr, Expr)>) -> Result<Literal> {
+        let mut map = Map::new();
+        for (key, value) in entries.into_iter() {
+            let key = self.evaluate(key)?;
+            self.check_map_key(&key)?;
+
+            let value = self.evaluate(value)?;
+            map.insert(key, value);
+        }
+
+        Ok(Literal::Map(Rc::new(RefCell::new(map))))
+    }
+
+    fn index_get(&self, object: &Literal, index: &Literal) -> Result<Literal> {
+        if let Literal::List(list) = object {
+            let position = self.list_index(list.borrow().len(), index)?;
+            return Ok(list.borrow()[position].clone());
+        } else if let Literal::Tuple(tuple) = object {
+            let position = self.list_index(tuple.len(), index)?;
+            return Ok(tuple[position].clone());
+        }
+
+        let map = self.map_key(object, index)?;
+        let value = map.borrow().get(index);
+        match value {
+            Some(value) => Ok(value),
+            None => self.runtime_error(
+                ErrorKind::KeyNotFound,
+                format!("key '{index}' is not in map"),
+            ),
+        }
+    }
+
+    fn index_set(&self, object: &Literal, index: Literal, value: Literal) -> Result<()> {
+        if let Literal::List(list) = object {
+            let position = self.list_index(list.borrow().len(), &index)?;
+            list.borrow_mut()[position] = value;
+            return Ok(());
+        } else if let Literal::Tuple(..) = object {
+            return self.runtime_error(ErrorKind::TypeMismatch, "tuples are immutable".to_string());
+        }
+
+        let map = self.map_key(object, &index)?;
+        map.borrow_mut().insert(index, value);
+        Ok(())
+    }
+
+    fn list_index(&self, len: usize, index: &Literal) -> Result<usize> {
+        let Literal::Int(index) = *index else {
+            let typename = self.typename_from_literal(index.clone());
+            return self.runtime_error(
+                ErrorKind::TypeMismatch,
+                format!("index must be an int, found '{typename}'"),
+            );
+        };
+
+        if index < 0 || index as usize >= len {
+            return self.runtime_error(
+                ErrorKind::IndexOutOfBounds,
+                format!("index {index} is out of bounds for length {len}"),
+            );
+        }
+
+        Ok(index as usize)
+    }
+
+    fn map_key(&self, object: &Literal, key: &Literal) -> Result<Rc<RefCell<Map>>> {
+        let Literal::Map(map) = object else {
+            let typename = self.typename_from_literal(object.clone());
+            return self.runtime_error(
+                ErrorKind::TypeMismatch,
+                format!("type '{typename}' is not indexable"),
+            );
+        };
+
+        self.check_map_key(key)?;
+        Ok(map.clone())
+    }
+
+    fn check_map_key(&self, key: &Literal) -> Result<()> {
+        if Map::is_key(key) {
+            return Ok(());
+        }
+
+        let typename = self.typename_from_literal(key.clone());
+        self.runtime_error(
+            ErrorKind::TypeMismatch,
+            format!("type '{typename}' cannot be used as a map key"),
+        )
+    }
+
+    fn map_method(&self, map: Literal, name: &str) -> Option<Literal> {
+        let arity = match name {
+            "has" | "remove" => 1,
+            "keys" => 0,
+            _ => return None,
+        };
+
+        let method = NativeMethod::new(map, name.to_string(), arity);
+        Some(Literal::NativeMethod(Rc::new(method)))
+    }
+
+    fn range_method(&self, range: Literal, name: &str) -> Option<Literal> {
+        let arity = match name {
+            "step" => 1,
+            _ => return None,
+        };
+
+        let method = NativeMethod::new(range, name.to_string(), arity);
+        Some(Literal::NativeMethod(Rc::new(method)))
+    }
+
+    fn call_native_method(
+        &mut self,
+        method: Rc<NativeMethod>,
+        arguments: Vec<Literal>,
+    ) -> Result<Literal> {
+        if arguments.len() != method.arity {
+            return self.runtime_error(
+                ErrorKind::ArityMismatch,
+                format!(
+                    "'{}' expected {} arguments but got {}",
+                    method.name,
+                    method.arity,
+                    arguments.len()
+                ),
+            );
+        }
+
+        match (&method.receiver, method.name.as_str()) {
+            (Literal::Map(map), "has") => {
+                self.check_map_key(&arguments[0])?;
+                Ok(Literal::Bool(map.borrow().contains(&arguments[0])))
+            }
+            (Literal::Map(map), "remove") => {
+                self.check_map_key(&arguments[0])?;
+                let removed = map.borrow_mut().remove(&arguments[0]);
+                Ok(removed.unwrap_or(Literal::Null))
+            }
+            (Literal::Map(map), "keys") => {
+                let keys = map.borrow().keys();
+                Ok(Literal::List(Rc::new(RefCell::new(keys))))
+            }
+            (Literal::Range(range), "step") => match arguments[0] {
+                Literal::Int(0) => self.runtime_error(
+                    ErrorKind::InvalidArgument,
+                    "range step cannot be zero".to_string(),
+                ),
+                Literal::Int(step) => Ok(Literal::Range(range.with_step(step))),
+                _ => {
+                    let typename = self.typename_from_literal(arguments[0].clone());
+                    self.runtime_error(
+                        ErrorKind::TypeMismatch,
+                        format!("range step must be an int, found '{typename}'"),
+                    )
+                }
+            },
+            (Literal::Enum(enumeration), name) => {
+                let variant = Variant::new(enumeration.clone(), name.to_string(), arguments);
+                Ok(Literal::Variant(Rc::new(variant)))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn call_function(
+        &mut self,
+        function: Rc<Function>,
+        arguments: Vec<Literal>,
+    ) -> Result<Literal> {
+        if arguments.len() != function.arity() {
+            return self.runtime_error(
+                ErrorKind::ArityMismatch,
+                format!(
+                    "'{}' expected {} arguments but got {}",
+                    function.name,
+                    function.arity(),
+                    arguments.len()
+                ),
+            );
+        }
+
+        if self.depth == MAX_CALL_DEPTH {
+            return self.runtime_error(
+                ErrorKind::StackOverflow,
+                format!(
+                    "'{}' exceeded the maximum call depth of {MAX_CALL_DEPTH}",
+                    function.name
+                ),
+            );
+        }
+
+        let mut environment = Environment::from(function.closure.clone());
+        for (param, argument) in function.params.iter().zip(arguments) {
+            environment.define(param.clone(), Some(argument));
+        }
+
+        self.depth += 1;
+        let result = self.execute_block(&function.body, environment);
+        self.depth -= 1;
+
+        match result? {
+            Flow::Return(value) => Ok(value),
+            _ => Ok(Literal::Null),
+        }
+    }
+
+    fn define_variable(&mut self, name: String, value: Option<Literal>) -> Result<()> {
+        if !self.environment.borrow_mut().define(name.clone(), value) {
+            return self.redeclaration_error(&name);
+        }
+
+        Ok(())
+    }
+
+    fn define_constant(&mut self, name: String, value: Literal) -> Result<()> {
+        if !self
+            .environment
+            .borrow_mut()
+            .define_constant(name.clone(), value)
+        {
+            return self.redeclaration_error(&name);
+        }
+
+        Ok(())
+    }
+
+    fn redeclaration_error(&self, name: &String) -> Result<()> {
+        self.runtime_error(
+            ErrorKind::ConstantReassignment,
+            format!("cannot redeclare constant '{name}'"),
+        )
+    }
+
+    fn is_truthy(&self, value: Literal) -> Result<Literal> {
+        let res = match value {
+            Literal::Int(x) => Literal::Bool(x > 0),
+            Literal::Number(x) => Literal::Bool(x > 0.0),
+            Literal::String(x) => Literal::Bool(!x.is_empty()),
+            Literal::Bool(x) => Literal::Bool(x),
+            Literal::Char(x) => Literal::Bool(x != '0'),
+            Literal::Function(..) => Literal::Bool(true),
+            Literal::Struct(..) => Literal::Bool(true),
+            Literal::Instance(..) => Literal::Bool(true),
+            Literal::Enum(..) => Literal::Bool(true),
+            Literal::Variant(..) => Literal::Bool(true),
+            Literal::List(x) => Literal::Bool(!x.borrow().is_empty()),
+            Literal::Tuple(x) => Literal::Bool(!x.is_empty()),
+            Literal::Range(x) => Literal::Bool(x.iter().next().is_some()),
+            Literal::Map(x) => Literal::Bool(!x.borrow().is_empty()),
+            Literal::NativeMethod(..) => Literal::Bool(true),
+            Literal::Module(..) => Literal::Bool(true),
             Literal::Null => Literal::Bool(false),
         };
 
@@ -304,7 +1772,15 @@ impl Interpreter {
     }
 
     fn negate(&self, value: Literal) -> Result<Literal> {
-        if let Literal::Number(x) = value {
+        if let Literal::Int(x) = value {
+            return match x.checked_neg() {
+                Some(res) => Ok(Literal::Int(res)),
+                None => self.runtime_error(
+                    ErrorKind::IntegerOverflow,
+                    format!("negating {x} overflows an int"),
+                ),
+            };
+        } else if let Literal::Number(x) = value {
             let res = Literal::Number(-x);
             return Ok(res);
         }
@@ -316,17 +1792,45 @@ impl Interpreter {
         )
     }
 
+    fn bit_not(&self, value: Literal) -> Result<Literal> {
+        if let Literal::Int(x) = value {
+            return Ok(Literal::Int(!x));
+        }
+
+        let typename = self.typename_from_literal(value);
+        self.runtime_error(
+            ErrorKind::OperatorNotDefined,
+            format!("unary bitwise not not supported for type '{typename}'"),
+        )
+    }
+
     fn typename_from_literal(&self, literal: Literal) -> &str {
         match literal {
-            Literal::Number(..) => "number",
+            Literal::Int(..) => "int",
+            Literal::Number(..) => "float",
             Literal::String(..) => "String",
             Literal::Bool(..) => "bool",
             Literal::Char(..) => "char",
+            Literal::Function(..) => "function",
+            Literal::Struct(..) => "struct",
+            Literal::Instance(..) => "instance",
+            Literal::Enum(..) => "enum",
+            Literal::Variant(..) => "variant",
+            Literal::List(..) => "list",
+            Literal::Tuple(..) => "tuple",
+            Literal::Range(..) => "range",
+            Literal::Map(..) => "map",
+            Literal::NativeMethod(..) => "function",
+            Literal::Module(..) => "module",
             Literal::Null => "null",
         }
     }
 
-    fn runtime_error(&self, kind: ErrorKind, msg: String) -> Result<Literal> {
-        Err(RuntimeError { what: msg, kind })
+    fn runtime_error<T>(&self, kind: ErrorKind, msg: String) -> Result<T> {
+        Err(RuntimeError {
+            what: msg,
+            kind,
+            value: None,
+        })
     }
 }
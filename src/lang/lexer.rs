@@ -1,13 +1,23 @@
 use std::collections::HashMap;
 
+use unicode_xid::UnicodeXID;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenType {
     LCurly,
     RCurly,
+    LBracket,
+    RBracket,
     LParen,
     RParen,
     Comma,
     Dot,
+    DotDot,
+    DotDotEq,
+    Ellipsis,
+    FatArrow,
+    Arrow,
+    Pipe,
     Colon,
     Semicolon,
     Eof,
@@ -33,18 +43,38 @@ pub enum TokenType {
     StarEq,
     Slash,
     SlashEq,
+    Percent,
+    PercentEq,
+    StarStar,
+    Amp,
+    Caret,
+    Tilde,
+    Shl,
+    Shr,
 
     // Keywords
     Print,
     If,
     Else,
     Let,
+    Const,
     Struct,
+    Enum,
+    Import,
     SSelf,
     While,
     For,
+    In,
     Return,
     Fun,
+    Loop,
+    Match,
+    Break,
+    Continue,
+    Try,
+    Catch,
+    Finally,
+    Throw,
     True,
     False,
     Null,
@@ -54,6 +84,12 @@ pub enum TokenType {
     Number,
     String,
     Char,
+    Label,
+
+    // Interpolated strings
+    InterpolationStart,
+    InterpolationPart,
+    InterpolationEnd,
 }
 
 #[derive(Debug, Clone)]
@@ -69,14 +105,13 @@ impl Token {
     }
 }
 
-impl ToString for Token {
-    fn to_string(&self) -> String {
-        format!("[{:?} {}]", self.ty, self.lexeme)
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{:?} {}]", self.ty, self.lexeme)
     }
 }
 
 pub struct Lexer {
-    source: String,
     chars: Vec<char>,
     cursor: usize,
     start: usize,
@@ -88,7 +123,6 @@ pub struct Lexer {
 impl Lexer {
     pub fn new(source: String) -> Self {
         Self {
-            source: source.clone(),
             chars: source.chars().collect(),
             cursor: 0,
             start: 0,
@@ -98,15 +132,25 @@ impl Lexer {
                 ("print", TokenType::Print),
                 ("if", TokenType::If),
                 ("else", TokenType::Else),
-                ("&&", TokenType::And),
-                ("||", TokenType::Or),
                 ("let", TokenType::Let),
+                ("const", TokenType::Const),
                 ("struct", TokenType::Struct),
+                ("enum", TokenType::Enum),
+                ("import", TokenType::Import),
                 ("self", TokenType::SSelf),
                 ("while", TokenType::While),
                 ("for", TokenType::For),
+                ("in", TokenType::In),
                 ("return", TokenType::Return),
                 ("fun", TokenType::Fun),
+                ("loop", TokenType::Loop),
+                ("match", TokenType::Match),
+                ("break", TokenType::Break),
+                ("continue", TokenType::Continue),
+                ("try", TokenType::Try),
+                ("catch", TokenType::Catch),
+                ("finally", TokenType::Finally),
+                ("throw", TokenType::Throw),
                 ("true", TokenType::True),
                 ("false", TokenType::False),
                 ("null", TokenType::Null),
@@ -139,10 +183,26 @@ impl Lexer {
         match c {
             '{' => self.add_token(TokenType::LCurly),
             '}' => self.add_token(TokenType::RCurly),
+            '[' => self.add_token(TokenType::LBracket),
+            ']' => self.add_token(TokenType::RBracket),
             '(' => self.add_token(TokenType::LParen),
             ')' => self.add_token(TokenType::RParen),
             ',' => self.add_token(TokenType::Comma),
-            '.' => self.add_token(TokenType::Dot),
+            '.' => {
+                if self.peek() == '.' && self.peek_next() == '.' {
+                    self.advance();
+                    self.advance();
+                    self.add_token(TokenType::Ellipsis)
+                } else if self.next_matches('.') {
+                    if self.next_matches('=') {
+                        self.add_token(TokenType::DotDotEq)
+                    } else {
+                        self.add_token(TokenType::DotDot)
+                    }
+                } else {
+                    self.add_token(TokenType::Dot)
+                }
+            }
             ':' => self.add_token(TokenType::Colon),
             ';' => self.add_token(TokenType::Semicolon),
             '+' => {
@@ -153,14 +213,18 @@ impl Lexer {
                 }
             }
             '-' => {
-                if self.next_matches('=') {
+                if self.next_matches('>') {
+                    self.add_token(TokenType::Arrow)
+                } else if self.next_matches('=') {
                     self.add_token(TokenType::MinusEq)
                 } else {
                     self.add_token(TokenType::Minus)
                 }
             }
             '*' => {
-                if self.next_matches('=') {
+                if self.next_matches('*') {
+                    self.add_token(TokenType::StarStar)
+                } else if self.next_matches('=') {
                     self.add_token(TokenType::StarEq)
                 } else {
                     self.add_token(TokenType::Star)
@@ -177,6 +241,13 @@ impl Lexer {
                     self.add_token(TokenType::Slash)
                 }
             }
+            '%' => {
+                if self.next_matches('=') {
+                    self.add_token(TokenType::PercentEq)
+                } else {
+                    self.add_token(TokenType::Percent)
+                }
+            }
             '!' => {
                 if self.next_matches('=') {
                     self.add_token(TokenType::Ne)
@@ -187,19 +258,41 @@ impl Lexer {
             '=' => {
                 if self.next_matches('=') {
                     self.add_token(TokenType::EqEq)
+                } else if self.next_matches('>') {
+                    self.add_token(TokenType::FatArrow)
                 } else {
                     self.add_token(TokenType::Eq)
                 }
             }
+            '&' => {
+                if self.next_matches('&') {
+                    self.add_token(TokenType::And)
+                } else {
+                    self.add_token(TokenType::Amp)
+                }
+            }
+            '^' => self.add_token(TokenType::Caret),
+            '~' => self.add_token(TokenType::Tilde),
+            '|' => {
+                if self.next_matches('|') {
+                    self.add_token(TokenType::Or)
+                } else {
+                    self.add_token(TokenType::Pipe)
+                }
+            }
             '<' => {
-                if self.next_matches('=') {
+                if self.next_matches('<') {
+                    self.add_token(TokenType::Shl)
+                } else if self.next_matches('=') {
                     self.add_token(TokenType::Lte)
                 } else {
                     self.add_token(TokenType::Lt)
                 }
             }
             '>' => {
-                if self.next_matches('=') {
+                if self.next_matches('>') {
+                    self.add_token(TokenType::Shr)
+                } else if self.next_matches('=') {
                     self.add_token(TokenType::Gte)
                 } else {
                     self.add_token(TokenType::Gt)
@@ -223,10 +316,14 @@ impl Lexer {
     }
 
     fn add_token(&mut self, ty: TokenType) {
-        let lexeme = String::from(&self.source[self.start..self.cursor]);
+        let lexeme = self.lexeme();
         self.tokens.push(Token::new(ty, lexeme, self.line))
     }
 
+    fn lexeme(&self) -> String {
+        self.chars[self.start..self.cursor].iter().collect()
+    }
+
     fn advance(&mut self) -> char {
         if self.is_at_end() {
             return 0 as char;
@@ -243,38 +340,174 @@ impl Lexer {
     }
 
     fn char(&mut self) {
-        self.advance();
+        let mut c = self.advance();
         self.start += 1;
 
+        if self.peek() != '\'' && self.is_alpha(c) {
+            self.label();
+            return;
+        }
+
+        if c == '\\' {
+            match self.escape() {
+                Some(escaped) => c = escaped,
+                None => return,
+            }
+        }
+
         if self.peek() != '\'' {
             self.error("Unterminated character literal".to_string());
             self.advance();
             return;
         }
 
-        self.add_token(TokenType::Char);
+        self.tokens
+            .push(Token::new(TokenType::Char, c.to_string(), self.line));
         self.advance();
     }
 
+    fn escape(&mut self) -> Option<char> {
+        let c = self.advance();
+
+        let escaped = match c {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            '\'' => '\'',
+            'u' => return self.unicode_escape(),
+            _ => {
+                self.error(format!("Unknown escape sequence '\\{c}'"));
+                return None;
+            }
+        };
+
+        Some(escaped)
+    }
+
+    fn unicode_escape(&mut self) -> Option<char> {
+        if !self.next_matches('{') {
+            self.error("Expected '{' after '\\u'".to_string());
+            return None;
+        }
+
+        let mut digits = String::new();
+        while !self.is_at_end() && self.peek().is_ascii_hexdigit() {
+            digits.push(self.advance());
+        }
+
+        if !self.next_matches('}') {
+            self.error("Expected '}' after unicode escape".to_string());
+            return None;
+        }
+
+        let escaped = u32::from_str_radix(&digits, 16)
+            .ok()
+            .filter(|_| digits.len() <= 6)
+            .and_then(char::from_u32);
+
+        if escaped.is_none() {
+            self.error(format!("Invalid unicode escape '\\u{{{digits}}}'"));
+        }
+
+        escaped
+    }
+
     fn string(&mut self) {
+        let mut segment = String::new();
+        let mut interpolated = false;
+
         while !self.is_at_end() && self.peek() != '"' {
-            self.advance();
+            let c = self.advance();
+
+            match c {
+                '\\' if matches!(self.peek(), '{' | '}') => segment.push(self.advance()),
+                '\\' => {
+                    if let Some(escaped) = self.escape() {
+                        segment.push(escaped);
+                    }
+                }
+                '{' => {
+                    let ty = if interpolated {
+                        TokenType::InterpolationPart
+                    } else {
+                        TokenType::InterpolationStart
+                    };
+
+                    self.tokens
+                        .push(Token::new(ty, std::mem::take(&mut segment), self.line));
+
+                    interpolated = true;
+                    self.interpolation();
+                }
+                '\n' => {
+                    self.line += 1;
+                    segment.push(c);
+                }
+                _ => segment.push(c),
+            }
         }
 
-        let error = || self.error("Unterminated string literal".to_string());
-
         if self.is_at_end() {
-            error();
-        } else if self.peek() == '"' {
-            self.advance();
+            self.error("Unterminated string literal".to_string());
+            return;
+        }
 
-            let lexeme = String::from(&self.source[self.start + 1..self.cursor - 1]);
+        self.advance();
 
-            self.tokens
-                .push(Token::new(TokenType::String, lexeme, self.line));
+        let ty = if interpolated {
+            TokenType::InterpolationEnd
         } else {
-            error();
+            TokenType::String
+        };
+
+        self.tokens.push(Token::new(ty, segment, self.line));
+    }
+
+    fn interpolation(&mut self) {
+        let start = self.cursor;
+        let mut depth = 0;
+
+        while !self.is_at_end() {
+            match self.peek() {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                '"' => {
+                    self.advance();
+                    while !self.is_at_end() && self.peek() != '"' {
+                        if self.advance() == '\\' {
+                            self.advance();
+                        }
+                    }
+                }
+                _ => (),
+            }
+
+            self.advance();
+        }
+
+        if self.is_at_end() {
+            self.error("Unterminated interpolation in string literal".to_string());
+            return;
+        }
+
+        let source: String = self.chars[start..self.cursor].iter().collect();
+        self.advance();
+
+        if source.trim().is_empty() {
+            self.error("Empty interpolation in string literal".to_string());
+            return;
         }
+
+        let mut lexer = Lexer::new(source);
+        lexer.line = self.line;
+
+        let tokens = lexer.scan_tokens();
+        self.tokens.extend_from_slice(&tokens[..tokens.len() - 1]);
+        self.line = lexer.line;
     }
 
     fn number(&mut self) {
@@ -287,7 +520,7 @@ impl Lexer {
             return;
         }
 
-        if self.peek() == '.' {
+        if self.peek() == '.' && self.is_digit(self.peek_next()) {
             self.advance();
 
             while !self.is_at_end() && self.is_digit(self.peek()) {
@@ -303,7 +536,7 @@ impl Lexer {
             self.advance();
         }
 
-        let lexeme = String::from(&self.source[self.start..self.cursor]);
+        let lexeme = self.lexeme();
 
         if self.keywords.contains_key(&lexeme) {
             self.add_token(*self.keywords.get(&lexeme).unwrap())
@@ -312,6 +545,14 @@ impl Lexer {
         }
     }
 
+    fn label(&mut self) {
+        while !self.is_at_end() && self.is_alnum(self.peek()) {
+            self.advance();
+        }
+
+        self.add_token(TokenType::Label);
+    }
+
     fn comment(&mut self) {
         while !self.is_at_end() && self.peek() != '\n' {
             self.advance();
@@ -354,22 +595,34 @@ impl Lexer {
     }
 
     fn peek(&self) -> char {
+        if self.cursor >= self.chars.len() {
+            return '\0';
+        }
+
         self.chars[self.cursor]
     }
 
+    fn peek_next(&self) -> char {
+        if self.cursor + 1 >= self.chars.len() {
+            return '\0';
+        }
+
+        self.chars[self.cursor + 1]
+    }
+
     fn is_digit(&self, c: char) -> bool {
         c.is_ascii_digit()
     }
 
     fn is_alpha(&self, c: char) -> bool {
-        c.is_ascii_lowercase() || c.is_ascii_uppercase() || "_&|".contains(c)
+        c.is_xid_start() || c == '_'
     }
 
     fn is_alnum(&self, c: char) -> bool {
-        self.is_digit(c) || self.is_alpha(c)
+        c.is_xid_continue() || self.is_alpha(c)
     }
 
     fn is_at_end(&self) -> bool {
-        self.cursor >= self.source.len()
+        self.cursor >= self.chars.len()
     }
 }
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use super::Literal;
+
+/// Hashable form of the literals that can be used as map keys.
+///
+/// Integral numbers are stored as `Int` so that `1` and `1.0` refer to the
+/// same entry, matching how `==` compares them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Int(i64),
+    Number(u64),
+    String(String),
+    Bool(bool),
+    Char(char),
+}
+
+impl Key {
+    fn from_literal(value: &Literal) -> Option<Self> {
+        match value {
+            Literal::Int(x) => Some(Key::Int(*x)),
+            Literal::Number(x) => {
+                if x.fract() == 0.0 && *x >= i64::MIN as f64 && *x < i64::MAX as f64 {
+                    Some(Key::Int(*x as i64))
+                } else {
+                    Some(Key::Number(x.to_bits()))
+                }
+            }
+            Literal::String(x) => Some(Key::String(x.clone())),
+            Literal::Bool(x) => Some(Key::Bool(*x)),
+            Literal::Char(x) => Some(Key::Char(*x)),
+            _ => None,
+        }
+    }
+}
+
+/// Map that keeps entries in insertion order, with hashed key lookup.
+#[derive(Debug, Default)]
+pub struct Map {
+    entries: Vec<(Literal, Literal)>,
+    indices: HashMap<Key, usize>,
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_key(value: &Literal) -> bool {
+        Key::from_literal(value).is_some()
+    }
+
+    pub fn get(&self, key: &Literal) -> Option<Literal> {
+        self.position(key).map(|i| self.entries[i].1.clone())
+    }
+
+    pub fn insert(&mut self, key: Literal, value: Literal) {
+        let Some(hashed) = Key::from_literal(&key) else {
+            return;
+        };
+
+        match self.indices.get(&hashed) {
+            Some(&i) => self.entries[i].1 = value,
+            None => {
+                self.indices.insert(hashed, self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    /// Removes `key`, shifting later entries down to preserve their order.
+    pub fn remove(&mut self, key: &Literal) -> Option<Literal> {
+        let i = self.indices.remove(&Key::from_literal(key)?)?;
+        for index in self.indices.values_mut() {
+            if *index > i {
+                *index -= 1;
+            }
+        }
+
+        Some(self.entries.remove(i).1)
+    }
+
+    pub fn contains(&self, key: &Literal) -> bool {
+        self.position(key).is_some()
+    }
+
+    pub fn keys(&self) -> Vec<Literal> {
+        self.entries.iter().map(|(key, _)| key.clone()).collect()
+    }
+
+    pub fn entries(&self) -> &[(Literal, Literal)] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn position(&self, key: &Literal) -> Option<usize> {
+        self.indices.get(&Key::from_literal(key)?).copied()
+    }
+}
@@ -1,15 +1,33 @@
+mod checker;
+mod enumeration;
 mod environment;
 mod expr;
+mod function;
 mod interpreter;
 mod lexer;
+mod map;
+mod module;
 mod parser;
+mod pattern;
+mod range;
 mod runtime_error;
 mod stmt;
+mod structure;
+mod types;
 
+pub use checker::*;
+pub use enumeration::*;
 pub use environment::*;
 pub use expr::*;
+pub use function::*;
 pub use interpreter::*;
 pub use lexer::*;
+pub use map::*;
+pub use module::*;
 pub use parser::*;
+pub use pattern::*;
+pub use range::*;
 pub use runtime_error::*;
 pub use stmt::*;
+pub use structure::*;
+pub use types::*;
@@ -0,0 +1,33 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::{Environment, Literal};
+
+#[derive(Debug, Clone)]
+pub enum Import {
+    Module(String),
+    Names(Vec<(String, String)>),
+}
+
+#[derive(Debug)]
+pub struct Module {
+    pub name: String,
+    pub environment: Rc<RefCell<Environment>>,
+}
+
+impl Module {
+    pub fn new(name: String, environment: Rc<RefCell<Environment>>) -> Self {
+        Self { name, environment }
+    }
+
+    pub fn has(&self, name: &String) -> bool {
+        self.environment.borrow().contains(name)
+    }
+
+    pub fn get(&self, name: &String) -> Option<Literal> {
+        self.environment.borrow().get(name)
+    }
+
+    pub fn is_constant(&self, name: &String) -> bool {
+        self.environment.borrow().is_constant(name)
+    }
+}
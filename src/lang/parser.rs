@@ -1,13 +1,45 @@
-use super::{BinaryOp, Expr, Literal, Stmt, Token, TokenType, UnaryOp, LogicalOp};
+use std::collections::HashMap;
+
+use super::{
+    ArmBody, BinaryOp, Expr, Import, Literal, LogicalOp, MatchArm, Pattern, Signature, Stmt, Token,
+    TokenType, Type, TypeAnnotation, UnaryOp,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LoopKind {
+    Loop,
+    LoopExpr,
+    While,
+    MatchExpr,
+}
+
+impl LoopKind {
+    fn is_expression(&self) -> bool {
+        matches!(self, LoopKind::LoopExpr | LoopKind::MatchExpr)
+    }
+}
 
 pub struct Parser<'a> {
     tokens: &'a Vec<Token>,
     index: usize,
+    function_depth: usize,
+    method_depth: usize,
+    loops: Vec<(Option<String>, LoopKind)>,
+    scopes: Vec<HashMap<String, bool>>,
+    struct_literals: bool,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokens: &'a Vec<Token>) -> Self {
-        Self { tokens, index: 0 }
+        Self {
+            tokens,
+            index: 0,
+            function_depth: 0,
+            method_depth: 0,
+            loops: Vec::new(),
+            scopes: vec![HashMap::new()],
+            struct_literals: true,
+        }
     }
 
     pub fn parse(&mut self) -> Vec<Stmt> {
@@ -32,117 +64,1226 @@ impl<'a> Parser<'a> {
         self.tokens[self.index].clone()
     }
 
+    fn peek_nth(&self, n: usize) -> Token {
+        let index = usize::min(self.index + n, self.tokens.len() - 1);
+        self.tokens[index].clone()
+    }
+
     fn previous(&self) -> Token {
         self.tokens[self.index - 1].clone()
     }
 
-    fn expression(&mut self) -> Expr {
-        self.assignment()
+    fn expression(&mut self) -> Expr {
+        self.assignment()
+    }
+
+    fn declaration(&mut self) -> Stmt {
+        if self.matches(vec![TokenType::Let]) {
+            return self.variable_declaration();
+        } else if self.matches(vec![TokenType::Const]) {
+            return self.constant_declaration();
+        } else if self.check(TokenType::Fun) && self.peek_nth(1).ty == TokenType::Ident {
+            self.advance();
+            return self.function_declaration();
+        } else if self.matches(vec![TokenType::Struct]) {
+            return self.struct_declaration();
+        } else if self.matches(vec![TokenType::Enum]) {
+            return self.enum_declaration();
+        } else if self.matches(vec![TokenType::Import]) {
+            return self.import_declaration();
+        }
+
+        self.statement()
+    }
+
+    fn statement(&mut self) -> Stmt {
+        if self.matches(vec![TokenType::If]) {
+            return self.if_statement();
+        } else if self.matches(vec![TokenType::Print]) {
+            return self.print_statement();
+        } else if self.matches(vec![TokenType::While]) {
+            return self.while_statement(None);
+        } else if self.matches(vec![TokenType::For]) {
+            return self.for_statement(None);
+        } else if self.matches(vec![TokenType::Loop]) {
+            return self.loop_statement(None);
+        } else if self.check(TokenType::Label) && self.peek_nth(1).ty == TokenType::Colon {
+            return self.labeled_statement();
+        } else if self.matches(vec![TokenType::Match]) {
+            return self.match_statement();
+        } else if self.matches(vec![TokenType::Return]) {
+            return self.return_statement();
+        } else if self.matches(vec![TokenType::Break]) {
+            return self.break_statement();
+        } else if self.matches(vec![TokenType::Continue]) {
+            return self.continue_statement();
+        } else if self.matches(vec![TokenType::Try]) {
+            return self.try_statement();
+        } else if self.matches(vec![TokenType::Throw]) {
+            return self.throw_statement();
+        } else if self.matches(vec![TokenType::LCurly]) {
+            return Stmt::Block(self.block());
+        }
+
+        self.expression_statement()
+    }
+
+    fn expression_statement(&mut self) -> Stmt {
+        let expr = self.expression();
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after expression".to_string(),
+        );
+
+        Stmt::Expr(Box::new(expr))
+    }
+
+    fn if_statement(&mut self) -> Stmt {
+        self.consume(
+            TokenType::LParen,
+            "Expected '(' before expression".to_string(),
+        );
+
+        let condition = self.expression();
+
+        self.consume(
+            TokenType::RParen,
+            "Expected ')' after expression".to_string(),
+        );
+
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' after condition".to_string(),
+        );
+
+        let main_branch = Stmt::Block(self.block());
+
+        let mut else_branch = None;
+        if self.matches(vec![TokenType::Else]) {
+            if self.matches(vec![TokenType::If]) {
+                else_branch = Some(Box::new(self.if_statement()));
+            } else {
+                self.consume(
+                    TokenType::LCurly,
+                    "Expected '{' or 'if' after else".to_string(),
+                );
+
+                else_branch = Some(Box::new(Stmt::Block(self.block())));
+            }
+        }
+
+        Stmt::If(Box::new(condition), Box::new(main_branch), else_branch)
+    }
+
+    fn while_statement(&mut self, label: Option<String>) -> Stmt {
+        self.consume(
+            TokenType::LParen,
+            "Expected '(' before expression".to_string(),
+        );
+
+        let condition = self.expression();
+
+        self.consume(
+            TokenType::RParen,
+            "Expected ')' after expression".to_string(),
+        );
+
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' after condition".to_string(),
+        );
+
+        let body = self.loop_body(label.clone(), LoopKind::While);
+
+        Stmt::While(Box::new(condition), Box::new(body), None, label)
+    }
+
+    fn for_statement(&mut self, label: Option<String>) -> Stmt {
+        if self.is_for_in() {
+            return self.for_in_statement(label);
+        }
+
+        self.consume(TokenType::LParen, "Expected '(' after 'for'".to_string());
+
+        self.begin_scope();
+
+        let initializer = if self.matches(vec![TokenType::Semicolon]) {
+            None
+        } else if self.matches(vec![TokenType::Let]) {
+            Some(self.variable_declaration())
+        } else {
+            Some(self.expression_statement())
+        };
+
+        let mut condition = Expr::Literal(Literal::Bool(true));
+        if !self.check(TokenType::Semicolon) {
+            condition = self.expression();
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after loop condition".to_string(),
+        );
+
+        let mut increment = None;
+        if !self.check(TokenType::RParen) {
+            increment = Some(self.expression());
+        }
+
+        self.consume(
+            TokenType::RParen,
+            "Expected ')' after for clauses".to_string(),
+        );
+
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' after for clauses".to_string(),
+        );
+
+        // Desugar into a while loop, wrapped in its own block so the loop
+        // variable doesn't leak into the enclosing scope
+        let body = self.loop_body(label.clone(), LoopKind::While);
+
+        let mut statements = Vec::new();
+        if let Some(initializer) = initializer {
+            statements.push(initializer);
+        }
+
+        statements.push(Stmt::While(
+            Box::new(condition),
+            Box::new(body),
+            increment.map(Box::new),
+            label,
+        ));
+
+        self.end_scope();
+
+        Stmt::Block(statements)
+    }
+
+    fn is_for_in(&self) -> bool {
+        if !self.check(TokenType::LParen) {
+            return true;
+        }
+
+        // A parenthesized tuple pattern is followed by 'in', C-style clauses are not
+        let mut depth = 0;
+        for n in 0.. {
+            match self.peek_nth(n).ty {
+                TokenType::LParen => depth += 1,
+                TokenType::RParen => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return self.peek_nth(n + 1).ty == TokenType::In;
+                    }
+                }
+                TokenType::Eof => break,
+                _ => (),
+            }
+        }
+
+        false
+    }
+
+    fn for_in_statement(&mut self, label: Option<String>) -> Stmt {
+        let pattern = self.pattern();
+
+        self.consume(
+            TokenType::In,
+            "Expected 'in' after loop pattern".to_string(),
+        );
+
+        // `for x in xs { ... }` would otherwise parse `xs { ... }` as a struct literal
+        let struct_literals = std::mem::replace(&mut self.struct_literals, false);
+        let iterable = self.expression();
+        self.struct_literals = struct_literals;
+
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' after for iterable".to_string(),
+        );
+
+        self.begin_scope();
+        for name in pattern.bindings() {
+            self.declare(name, false);
+        }
+
+        let body = self.loop_body(label.clone(), LoopKind::While);
+
+        self.end_scope();
+
+        Stmt::ForIn(pattern, Box::new(iterable), Box::new(body), label)
+    }
+
+    fn loop_statement(&mut self, label: Option<String>) -> Stmt {
+        self.consume(TokenType::LCurly, "Expected '{' after 'loop'".to_string());
+
+        let body = self.loop_body(label.clone(), LoopKind::Loop);
+
+        Stmt::Loop(Box::new(body), label)
+    }
+
+    fn loop_expression(&mut self, label: Option<String>) -> Expr {
+        self.consume(TokenType::LCurly, "Expected '{' after 'loop'".to_string());
+
+        let body = self.loop_body(label.clone(), LoopKind::LoopExpr);
+
+        Expr::Loop(Box::new(body), label)
+    }
+
+    fn loop_body(&mut self, label: Option<String>, kind: LoopKind) -> Stmt {
+        self.loops.push((label, kind));
+        let body = Stmt::Block(self.block());
+        self.loops.pop();
+
+        body
+    }
+
+    fn labeled_statement(&mut self) -> Stmt {
+        let label = self.advance().lexeme;
+        self.advance();
+
+        if self.matches(vec![TokenType::While]) {
+            return self.while_statement(Some(label));
+        } else if self.matches(vec![TokenType::For]) {
+            return self.for_statement(Some(label));
+        } else if self.matches(vec![TokenType::Loop]) {
+            return self.loop_statement(Some(label));
+        }
+
+        self.error(self.peek(), "Expected loop after label".to_string());
+        Stmt::Block(Vec::new())
+    }
+
+    fn break_statement(&mut self) -> Stmt {
+        let keyword = self.previous();
+
+        let mut label = None;
+        if self.matches(vec![TokenType::Label]) {
+            label = Some(self.previous().lexeme);
+        }
+
+        let mut value = None;
+        if !self.check(TokenType::Semicolon) {
+            value = Some(Box::new(self.expression()));
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after 'break'".to_string(),
+        );
+
+        if let Some(kind) = self.resolve_loop(&keyword, &label) {
+            if kind == LoopKind::While && value.is_some() {
+                self.report(
+                    keyword,
+                    "'break' with a value is only allowed inside 'loop'".to_string(),
+                );
+            }
+        }
+
+        Stmt::Break(label, value)
+    }
+
+    fn continue_statement(&mut self) -> Stmt {
+        let keyword = self.previous();
+
+        let mut label = None;
+        if self.matches(vec![TokenType::Label]) {
+            label = Some(self.previous().lexeme);
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after 'continue'".to_string(),
+        );
+
+        self.resolve_loop(&keyword, &label);
+
+        Stmt::Continue(label)
+    }
+
+    fn resolve_loop(&self, keyword: &Token, label: &Option<String>) -> Option<LoopKind> {
+        let target = match label {
+            Some(label) => self
+                .loops
+                .iter()
+                .rposition(|(name, _)| name.as_ref() == Some(label)),
+            None => self
+                .loops
+                .iter()
+                .rposition(|(_, kind)| *kind != LoopKind::MatchExpr),
+        };
+
+        let Some(target) = target else {
+            match label {
+                Some(label) => {
+                    self.report(keyword.clone(), format!("use of undeclared label '{label}"))
+                }
+                None => self.report(
+                    keyword.clone(),
+                    format!("'{}' outside of loop", keyword.lexeme),
+                ),
+            }
+
+            return None;
+        };
+
+        // Loop and match expressions yield a value, so control flow can't jump past them
+        let crosses_expression = self.loops[target + 1..]
+            .iter()
+            .any(|(_, kind)| kind.is_expression());
+
+        if crosses_expression {
+            self.report(
+                keyword.clone(),
+                format!(
+                    "cannot '{}' out of a loop or match expression",
+                    keyword.lexeme
+                ),
+            );
+        }
+
+        Some(self.loops[target].1)
+    }
+
+    fn try_statement(&mut self) -> Stmt {
+        let keyword = self.previous();
+
+        self.consume(TokenType::LCurly, "Expected '{' after 'try'".to_string());
+        let body = self.block();
+
+        let mut handler = None;
+        if self.matches(vec![TokenType::Catch]) {
+            self.consume(TokenType::LParen, "Expected '(' after 'catch'".to_string());
+
+            let name = self
+                .consume(TokenType::Ident, "Expected error name".to_string())
+                .map(|token| token.lexeme)
+                .unwrap_or_default();
+
+            self.consume(
+                TokenType::RParen,
+                "Expected ')' after error name".to_string(),
+            );
+
+            self.consume(
+                TokenType::LCurly,
+                "Expected '{' before catch body".to_string(),
+            );
+
+            self.begin_scope();
+            self.declare(name.clone(), false);
+            let statements = self.block();
+            self.end_scope();
+
+            handler = Some((name, statements));
+        }
+
+        let mut finalizer = None;
+        if self.matches(vec![TokenType::Finally]) {
+            self.consume(
+                TokenType::LCurly,
+                "Expected '{' after 'finally'".to_string(),
+            );
+
+            finalizer = Some(self.block());
+        }
+
+        if handler.is_none() && finalizer.is_none() {
+            self.report(
+                keyword,
+                "Expected 'catch' or 'finally' after try block".to_string(),
+            );
+        }
+
+        Stmt::Try(body, handler, finalizer)
+    }
+
+    fn throw_statement(&mut self) -> Stmt {
+        let value = self.expression();
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after thrown value".to_string(),
+        );
+
+        Stmt::Throw(Box::new(value))
+    }
+
+    fn return_statement(&mut self) -> Stmt {
+        let keyword = self.previous();
+
+        if self.function_depth == 0 {
+            let line = keyword.line;
+            self.error(keyword, "'return' outside of function".to_string());
+            return Stmt::Return(None, line);
+        }
+
+        let mut value = None;
+        if !self.check(TokenType::Semicolon) {
+            value = Some(Box::new(self.expression()));
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after return value".to_string(),
+        );
+
+        Stmt::Return(value, keyword.line)
+    }
+
+    fn function_declaration(&mut self) -> Stmt {
+        let name = self
+            .consume(TokenType::Ident, "Expected function name".to_string())
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
+
+        self.declare(name.clone(), false);
+
+        self.function(name)
+    }
+
+    fn function(&mut self, name: String) -> Stmt {
+        self.consume(
+            TokenType::LParen,
+            "Expected '(' after function name".to_string(),
+        );
+
+        let (params, body, signature) = self.function_rest();
+        Stmt::Function(name, params, body, signature)
+    }
+
+    fn function_rest(&mut self) -> (Vec<String>, Vec<Stmt>, Signature) {
+        let (params, param_types) = self.parameters(TokenType::RParen);
+
+        self.consume(
+            TokenType::RParen,
+            "Expected ')' after parameters".to_string(),
+        );
+
+        let mut ret = None;
+        if self.matches(vec![TokenType::Arrow]) {
+            ret = self.type_annotation();
+        }
+
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' before function body".to_string(),
+        );
+
+        let body = self.function_body(&params, |parser| parser.block());
+        let signature = Signature {
+            params: param_types,
+            ret,
+        };
+
+        (params, body, signature)
+    }
+
+    fn parameters(&mut self, closing: TokenType) -> (Vec<String>, Vec<Option<TypeAnnotation>>) {
+        let mut params = Vec::new();
+        let mut types = Vec::new();
+
+        if !self.check(closing) {
+            loop {
+                let param = self
+                    .consume(TokenType::Ident, "Expected parameter name".to_string())
+                    .map(|token| token.lexeme)
+                    .unwrap_or_default();
+                params.push(param);
+
+                let mut annotation = None;
+                if self.matches(vec![TokenType::Colon]) {
+                    annotation = self.type_annotation();
+                }
+                types.push(annotation);
+
+                if !self.matches(vec![TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        (params, types)
+    }
+
+    fn type_annotation(&mut self) -> Option<TypeAnnotation> {
+        let token = if self.matches(vec![TokenType::Null]) {
+            self.previous()
+        } else {
+            self.consume(TokenType::Ident, "Expected type name".to_string())?
+        };
+
+        Some(TypeAnnotation {
+            ty: Type::from_name(&token.lexeme),
+            line: token.line,
+        })
+    }
+
+    fn function_body<T>(&mut self, params: &[String], body: impl FnOnce(&mut Self) -> T) -> T {
+        self.begin_scope();
+        for param in params.iter() {
+            self.declare(param.clone(), false);
+        }
+
+        let loops = std::mem::take(&mut self.loops);
+        let struct_literals = std::mem::replace(&mut self.struct_literals, true);
+
+        self.function_depth += 1;
+        let body = body(self);
+        self.function_depth -= 1;
+
+        self.struct_literals = struct_literals;
+        self.loops = loops;
+
+        self.end_scope();
+
+        body
+    }
+
+    fn lambda(&mut self) -> Expr {
+        self.consume(TokenType::LParen, "Expected '(' after 'fun'".to_string());
+
+        let (params, body, signature) = self.function_rest();
+        Expr::Lambda(params, body, signature)
+    }
+
+    fn short_lambda(&mut self) -> Expr {
+        let mut params = Vec::new();
+        let mut signature = Signature::default();
+
+        // `||` is lexed as a single token, which is a lambda without parameters
+        if self.previous().ty == TokenType::Pipe {
+            (params, signature.params) = self.parameters(TokenType::Pipe);
+
+            self.consume(TokenType::Pipe, "Expected '|' after parameters".to_string());
+        }
+
+        let line = self.previous().line;
+        let body = self.function_body(&params, |parser| {
+            let value = parser.expression();
+            vec![Stmt::Return(Some(Box::new(value)), line)]
+        });
+
+        Expr::Lambda(params, body, signature)
+    }
+
+    fn import_declaration(&mut self) -> Stmt {
+        if self.matches(vec![TokenType::LCurly]) {
+            let mut names = Vec::new();
+
+            while !self.check(TokenType::RCurly) && !self.is_at_end() {
+                let name = self
+                    .consume(TokenType::Ident, "Expected name to import".to_string())
+                    .map(|token| token.lexeme)
+                    .unwrap_or_default();
+
+                let mut alias = name.clone();
+                if self.matches_word("as") {
+                    alias = self
+                        .consume(TokenType::Ident, "Expected name after 'as'".to_string())
+                        .map(|token| token.lexeme)
+                        .unwrap_or_default();
+                }
+
+                self.declare(alias.clone(), false);
+                names.push((name, alias));
+
+                if !self.matches(vec![TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(
+                TokenType::RCurly,
+                "Expected '}' after imported names".to_string(),
+            );
+
+            if !self.matches_word("from") {
+                self.error(
+                    self.peek(),
+                    "Expected 'from' after imported names".to_string(),
+                );
+                return Stmt::Block(Vec::new());
+            }
+
+            let path = self.module_path();
+
+            self.consume(
+                TokenType::Semicolon,
+                "Expected ';' after import".to_string(),
+            );
+
+            return Stmt::Import(path, Import::Names(names));
+        }
+
+        let path = self.module_path();
+
+        if !self.matches_word("as") {
+            self.error(self.peek(), "Expected 'as' after module path".to_string());
+            return Stmt::Block(Vec::new());
+        }
+
+        let alias = self
+            .consume(
+                TokenType::Ident,
+                "Expected module name after 'as'".to_string(),
+            )
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
+
+        self.declare(alias.clone(), false);
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after import".to_string(),
+        );
+
+        Stmt::Import(path, Import::Module(alias))
+    }
+
+    fn module_path(&mut self) -> String {
+        self.consume(TokenType::String, "Expected module path".to_string())
+            .map(|token| token.lexeme)
+            .unwrap_or_default()
+    }
+
+    fn struct_declaration(&mut self) -> Stmt {
+        let name = self
+            .consume(TokenType::Ident, "Expected struct name".to_string())
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
+
+        self.declare(name.clone(), false);
+
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' before struct body".to_string(),
+        );
+
+        let mut fields = Vec::new();
+        let mut methods = Vec::new();
+
+        while !self.check(TokenType::RCurly) && !self.is_at_end() {
+            if self.matches(vec![TokenType::Fun]) {
+                let method = self
+                    .consume(TokenType::Ident, "Expected method name".to_string())
+                    .map(|token| token.lexeme)
+                    .unwrap_or_default();
+
+                self.method_depth += 1;
+                methods.push(self.function(method));
+                self.method_depth -= 1;
+            } else {
+                let field = self
+                    .consume(TokenType::Ident, "Expected field name".to_string())
+                    .map(|token| token.lexeme)
+                    .unwrap_or_default();
+                fields.push(field);
+
+                if !self.check(TokenType::RCurly) && !self.check(TokenType::Fun) {
+                    self.consume(TokenType::Comma, "Expected ',' after field".to_string());
+                }
+            }
+        }
+
+        self.consume(
+            TokenType::RCurly,
+            "Expected '}' after struct body".to_string(),
+        );
+
+        Stmt::Struct(name, fields, methods)
+    }
+
+    fn enum_declaration(&mut self) -> Stmt {
+        let name = self
+            .consume(TokenType::Ident, "Expected enum name".to_string())
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
+
+        self.declare(name.clone(), false);
+
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' before enum body".to_string(),
+        );
+
+        let mut variants: Vec<(String, Vec<String>)> = Vec::new();
+
+        while !self.check(TokenType::RCurly) && !self.is_at_end() {
+            let Some(variant) = self.consume(TokenType::Ident, "Expected variant name".to_string())
+            else {
+                break;
+            };
+
+            if variants.iter().any(|(name, _)| *name == variant.lexeme) {
+                self.report(
+                    variant.clone(),
+                    format!("duplicate variant '{}'", variant.lexeme),
+                );
+            }
+
+            let mut fields = Vec::new();
+            if self.matches(vec![TokenType::LParen]) {
+                (fields, _) = self.parameters(TokenType::RParen);
+
+                self.consume(
+                    TokenType::RParen,
+                    "Expected ')' after variant fields".to_string(),
+                );
+
+                // `.variant` always reads the variant name, a field can't take it over
+                if fields.iter().any(|field| field == "variant") {
+                    self.report(
+                        variant.clone(),
+                        "'variant' can't be used as a variant field name".to_string(),
+                    );
+                }
+            }
+
+            variants.push((variant.lexeme, fields));
+
+            if !self.check(TokenType::RCurly) {
+                self.consume(TokenType::Comma, "Expected ',' after variant".to_string());
+            }
+        }
+
+        self.consume(
+            TokenType::RCurly,
+            "Expected '}' after enum body".to_string(),
+        );
+
+        Stmt::Enum(name, variants)
+    }
+
+    fn variable_declaration(&mut self) -> Stmt {
+        let variant = self.check(TokenType::Ident) && self.peek_nth(1).ty == TokenType::Dot;
+        if self.check(TokenType::LParen) || self.check(TokenType::LBracket) || variant {
+            return self.destructuring_declaration();
+        }
+
+        let name = self
+            .consume(TokenType::Ident, "Expected identifier".to_string())
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
+
+        let mut annotation = None;
+        if self.matches(vec![TokenType::Colon]) {
+            annotation = self.type_annotation();
+        }
+
+        let mut initializer = None;
+        if self.matches(vec![TokenType::Eq]) {
+            initializer = Some(Box::new(self.expression()));
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after declaration".to_string(),
+        );
+
+        self.declare(name.clone(), false);
+
+        Stmt::Let(name, initializer, annotation)
+    }
+
+    fn destructuring_declaration(&mut self) -> Stmt {
+        let pattern = self.pattern();
+
+        self.consume(
+            TokenType::Eq,
+            "Expected '=' after pattern, destructuring requires an initializer".to_string(),
+        );
+
+        let initializer = self.expression();
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after declaration".to_string(),
+        );
+
+        for name in pattern.bindings() {
+            self.declare(name, false);
+        }
+
+        Stmt::Destructure(pattern, Box::new(initializer))
+    }
+
+    fn pattern(&mut self) -> Pattern {
+        let first = self.single_pattern();
+
+        if !self.check(TokenType::Pipe) {
+            return first;
+        }
+
+        let mut alternatives = vec![first];
+        while self.matches(vec![TokenType::Pipe]) {
+            alternatives.push(self.single_pattern());
+        }
+
+        let mut expected = alternatives[0].bindings();
+        expected.sort();
+
+        for alternative in alternatives.iter().skip(1) {
+            let mut names = alternative.bindings();
+            names.sort();
+
+            if names != expected {
+                self.report(
+                    self.previous(),
+                    "all alternatives of a pattern must bind the same names".to_string(),
+                );
+                break;
+            }
+        }
+
+        Pattern::Or(alternatives)
+    }
+
+    fn single_pattern(&mut self) -> Pattern {
+        if self.matches(vec![TokenType::LParen]) {
+            let mut elements = Vec::new();
+
+            if !self.check(TokenType::RParen) {
+                loop {
+                    elements.push(self.pattern());
+
+                    if !self.matches(vec![TokenType::Comma]) || self.check(TokenType::RParen) {
+                        break;
+                    }
+                }
+            }
+
+            self.consume(
+                TokenType::RParen,
+                "Expected ')' after tuple pattern".to_string(),
+            );
+
+            return Pattern::Tuple(elements);
+        } else if self.matches(vec![TokenType::LBracket]) {
+            let mut elements = Vec::new();
+            let mut rest = None;
+
+            if !self.check(TokenType::RBracket) {
+                loop {
+                    if self.check(TokenType::Ident) && self.peek_nth(1).ty == TokenType::Ellipsis {
+                        rest = Some(self.advance().lexeme);
+                        self.advance();
+                        break;
+                    }
+
+                    elements.push(self.pattern());
+
+                    if !self.matches(vec![TokenType::Comma]) || self.check(TokenType::RBracket) {
+                        break;
+                    }
+                }
+            }
+
+            self.consume(
+                TokenType::RBracket,
+                "Expected ']' after list pattern".to_string(),
+            );
+
+            return Pattern::List(elements, rest);
+        } else if let Some(literal) = self.pattern_literal() {
+            if self.matches(vec![TokenType::DotDot, TokenType::DotDotEq]) {
+                let inclusive = self.previous().ty == TokenType::DotDotEq;
+
+                let Some(end) = self.pattern_literal() else {
+                    self.report(self.peek(), "Expected literal after '..'".to_string());
+                    return Pattern::Wildcard;
+                };
+
+                return Pattern::Range(literal, end, inclusive);
+            }
+
+            return Pattern::Literal(literal);
+        }
+
+        let name = self
+            .consume(TokenType::Ident, "Expected pattern".to_string())
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
+
+        if name == "_" {
+            return Pattern::Wildcard;
+        }
+
+        if self.matches(vec![TokenType::Dot]) {
+            return self.variant_pattern(vec![name]);
+        }
+
+        Pattern::Binding(name)
+    }
+
+    fn variant_pattern(&mut self, mut path: Vec<String>) -> Pattern {
+        let mut variant = self
+            .consume(
+                TokenType::Ident,
+                "Expected variant name after '.'".to_string(),
+            )
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
+
+        // Enums imported as part of a module are matched as `module.Enum.Variant`
+        while self.matches(vec![TokenType::Dot]) {
+            path.push(variant);
+            variant = self
+                .consume(
+                    TokenType::Ident,
+                    "Expected variant name after '.'".to_string(),
+                )
+                .map(|token| token.lexeme)
+                .unwrap_or_default();
+        }
+
+        if !self.matches(vec![TokenType::LParen]) {
+            return Pattern::Variant(path, variant, None);
+        }
+
+        let mut payload = Vec::new();
+        if !self.check(TokenType::RParen) {
+            loop {
+                payload.push(self.pattern());
+
+                if !self.matches(vec![TokenType::Comma]) || self.check(TokenType::RParen) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(
+            TokenType::RParen,
+            "Expected ')' after variant pattern".to_string(),
+        );
+
+        Pattern::Variant(path, variant, Some(payload))
     }
 
-    fn declaration(&mut self) -> Stmt {
-        if self.matches(vec![TokenType::Let]) {
-            return self.variable_declaration();
+    fn pattern_literal(&mut self) -> Option<Literal> {
+        if self.check(TokenType::Minus) && self.peek_nth(1).ty == TokenType::Number {
+            self.advance();
+            let token = self.advance();
+            return Some(self.number_literal(&token, true));
+        } else if self.matches(vec![TokenType::Number]) {
+            return Some(self.number_literal(&self.previous(), false));
+        } else if self.matches(vec![TokenType::String]) {
+            return Some(Literal::String(self.previous().lexeme));
+        } else if self.matches(vec![TokenType::Char]) {
+            let character = self.previous().lexeme.parse::<char>().unwrap();
+            return Some(Literal::Char(character));
+        } else if self.matches(vec![TokenType::True]) {
+            return Some(Literal::Bool(true));
+        } else if self.matches(vec![TokenType::False]) {
+            return Some(Literal::Bool(false));
+        } else if self.matches(vec![TokenType::Null]) {
+            return Some(Literal::Null);
         }
 
-        self.statement()
+        None
     }
 
-    fn statement(&mut self) -> Stmt {
-        if self.matches(vec![TokenType::If]) {
-            return self.if_statement();
-        } else if self.matches(vec![TokenType::Print]) {
-            return self.print_statement();
-        } else if self.matches(vec![TokenType::LCurly]) {
-            return Stmt::Block(self.block());
+    fn number_literal(&self, token: &Token, negative: bool) -> Literal {
+        let sign = if negative { "-" } else { "" };
+        let lexeme = format!("{sign}{}", token.lexeme);
+
+        if lexeme.contains('.') {
+            return Literal::Number(lexeme.parse::<f64>().unwrap());
         }
 
-        self.expression_statement()
+        match lexeme.parse::<i64>() {
+            Ok(value) => Literal::Int(value),
+            Err(_) => {
+                self.report(token.clone(), "integer literal is too large".to_string());
+                Literal::Int(0)
+            }
+        }
     }
 
-    fn expression_statement(&mut self) -> Stmt {
-        let expr = self.expression();
-        self.consume(
-            TokenType::Semicolon,
-            "Expected ';' after expression".to_string(),
-        );
-
-        Stmt::Expr(Box::new(expr))
-    }
+    fn match_arms(&mut self) -> (Expr, Vec<MatchArm>) {
+        // `match x { ... }` would otherwise parse `x { ... }` as a struct literal
+        let struct_literals = std::mem::replace(&mut self.struct_literals, false);
+        let value = self.expression();
+        self.struct_literals = struct_literals;
 
-    fn if_statement(&mut self) -> Stmt {
         self.consume(
-            TokenType::LParen,
-            "Expected '(' before expression".to_string(),
+            TokenType::LCurly,
+            "Expected '{' after match value".to_string(),
         );
 
-        let condition = self.expression();
+        let mut arms = Vec::new();
+        while !self.check(TokenType::RCurly) && !self.is_at_end() {
+            let pattern = self.pattern();
 
-        self.consume(
-            TokenType::RParen,
-            "Expected ')' after expression".to_string(),
-        );
+            self.begin_scope();
+            for name in pattern.bindings() {
+                self.declare(name, false);
+            }
 
-        self.consume(
-            TokenType::LCurly,
-            "Expected '{' after condition".to_string(),
-        );
+            let mut guard = None;
+            if self.matches(vec![TokenType::If]) {
+                guard = Some(self.expression());
+            }
+
+            self.consume(
+                TokenType::FatArrow,
+                "Expected '=>' after match pattern".to_string(),
+            );
 
-        let main_branch = self.statement();
+            let body = if self.matches(vec![TokenType::LCurly]) {
+                ArmBody::Block(self.block())
+            } else {
+                ArmBody::Expr(self.expression())
+            };
+
+            self.end_scope();
+
+            let is_block = matches!(body, ArmBody::Block(..));
+            arms.push(MatchArm {
+                pattern,
+                guard,
+                body,
+            });
+
+            if !self.matches(vec![TokenType::Comma]) && !is_block && !self.check(TokenType::RCurly)
+            {
+                self.error(self.peek(), "Expected ',' after match arm".to_string());
+                break;
+            }
+        }
 
         self.consume(
             TokenType::RCurly,
-            "Expected '}' after statement".to_string(),
+            "Expected '}' after match arms".to_string(),
         );
 
-        let mut else_branch = None;
-        if self.matches(vec![TokenType::Else]) {
-            self.consume(
-                TokenType::LCurly,
-                "Expected '{' after else branch".to_string(),
-            );
-
-            else_branch = Some(Box::new(self.statement()));
+        (value, arms)
+    }
 
-            self.consume(
-                TokenType::RCurly,
-                "Expected '}' after statement".to_string(),
-            );
+    fn interpolation(&mut self) -> Expr {
+        let mut parts = vec![Expr::Literal(Literal::String(self.previous().lexeme))];
+
+        loop {
+            parts.push(self.expression());
+
+            if self.matches(vec![TokenType::InterpolationPart]) {
+                parts.push(Expr::Literal(Literal::String(self.previous().lexeme)));
+            } else if self.matches(vec![TokenType::InterpolationEnd]) {
+                parts.push(Expr::Literal(Literal::String(self.previous().lexeme)));
+                break;
+            } else {
+                self.error(
+                    self.peek(),
+                    "Expected '}' after interpolated expression".to_string(),
+                );
+                break;
+            }
         }
 
-        Stmt::If(Box::new(condition), Box::new(main_branch), else_branch)
+        Expr::Interpolation(parts)
     }
 
-    fn variable_declaration(&mut self) -> Stmt {
+    fn match_statement(&mut self) -> Stmt {
+        let (value, arms) = self.match_arms();
+        Stmt::Match(Box::new(value), arms)
+    }
+
+    fn match_expression(&mut self) -> Expr {
+        self.loops.push((None, LoopKind::MatchExpr));
+        let (value, arms) = self.match_arms();
+        self.loops.pop();
+
+        Expr::Match(Box::new(value), arms)
+    }
+
+    fn constant_declaration(&mut self) -> Stmt {
         let name = self
             .consume(TokenType::Ident, "Expected identifier".to_string())
-            .unwrap();
+            .map(|token| token.lexeme)
+            .unwrap_or_default();
 
-        let mut initializer = None;
-        if self.matches(vec![TokenType::Eq]) {
-            initializer = Some(Box::new(self.expression()));
+        let mut annotation = None;
+        if self.matches(vec![TokenType::Colon]) {
+            annotation = self.type_annotation();
+        }
+
+        if self
+            .consume(
+                TokenType::Eq,
+                "Expected '=' after constant name, constants must be initialized".to_string(),
+            )
+            .is_none()
+        {
+            return Stmt::Const(name, Box::new(Expr::Literal(Literal::Null)), annotation);
         }
 
+        let initializer = self.expression();
+
         self.consume(
             TokenType::Semicolon,
             "Expected ';' after declaration".to_string(),
         );
 
-        Stmt::Let(name.lexeme, initializer)
+        self.declare(name.clone(), true);
+
+        Stmt::Const(name, Box::new(initializer), annotation)
     }
 
     fn block(&mut self) -> Vec<Stmt> {
         let mut statements = Vec::new();
 
+        self.begin_scope();
+
         while !self.check(TokenType::RCurly) && !self.is_at_end() {
             statements.push(self.declaration());
         }
 
+        self.end_scope();
+
         self.consume(TokenType::RCurly, "Expected '}' after block".to_string());
 
         statements
     }
 
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: String, constant: bool) {
+        if self
+            .scopes
+            .last()
+            .is_some_and(|scope| scope.get(&name) == Some(&true))
+        {
+            self.report(
+                self.previous(),
+                format!("cannot redeclare constant '{name}'"),
+            );
+            return;
+        }
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, constant);
+        }
+    }
+
+    fn is_constant(&self, name: &String) -> bool {
+        for scope in self.scopes.iter().rev() {
+            if let Some(constant) = scope.get(name) {
+                return *constant;
+            }
+        }
+
+        false
+    }
+
     fn print_statement(&mut self) -> Stmt {
         let expr = self.expression();
         self.consume(
@@ -154,17 +1295,65 @@ impl<'a> Parser<'a> {
     }
 
     fn assignment(&mut self) -> Expr {
-        let expr = self.or();
+        let expr = self.range();
 
         if self.matches(vec![TokenType::Eq]) {
             let equals_op = self.previous();
             let value = self.assignment();
 
             if let Expr::Variable(name) = expr {
-                return Expr::Assign(name, Box::new(value));
+                if self.is_constant(&name) {
+                    self.report(
+                        equals_op.clone(),
+                        format!("cannot assign to constant '{name}'"),
+                    );
+                }
+
+                return Expr::Assign(name, Box::new(value), equals_op.line);
+            } else if let Expr::Get(object, name) = expr {
+                return Expr::Set(object, name, Box::new(value));
+            } else if let Expr::Index(object, index) = expr {
+                return Expr::SetIndex(object, index, Box::new(value));
+            }
+
+            self.error(equals_op, "lvalue required".to_string());
+        } else if self.matches(vec![
+            TokenType::PlusEq,
+            TokenType::MinusEq,
+            TokenType::StarEq,
+            TokenType::SlashEq,
+            TokenType::PercentEq,
+        ]) {
+            let compound_op = self.previous();
+            let operator = BinaryOp::from_compound(compound_op.ty);
+            let value = self.assignment();
+
+            match &expr {
+                Expr::Variable(name) => {
+                    if self.is_constant(name) {
+                        self.report(compound_op, format!("cannot assign to constant '{name}'"));
+                    }
+                }
+                Expr::Get(..) | Expr::Index(..) => (),
+                _ => {
+                    self.report(compound_op, "lvalue required".to_string());
+                    return expr;
+                }
             }
 
-            self.error(equals_op, format!("lvalue required"));
+            return Expr::CompoundAssign(Box::new(expr), operator, Box::new(value));
+        }
+
+        expr
+    }
+
+    fn range(&mut self) -> Expr {
+        let expr = self.or();
+
+        if self.matches(vec![TokenType::DotDot, TokenType::DotDotEq]) {
+            let inclusive = self.previous().ty == TokenType::DotDotEq;
+            let end = self.or();
+            return Expr::Range(Box::new(expr), Box::new(end), inclusive);
         }
 
         expr
@@ -198,9 +1387,10 @@ impl<'a> Parser<'a> {
         let mut expr = self.comparison();
 
         while self.matches(vec![TokenType::Ne, TokenType::EqEq]) {
+            let line = self.previous().line;
             let operator = BinaryOp::from(self.previous().ty);
             let rhs = self.comparison();
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs));
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
         }
 
         expr
@@ -215,56 +1405,175 @@ impl<'a> Parser<'a> {
             TokenType::Lt,
             TokenType::Lte,
         ]) {
+            let line = self.previous().line;
             let operator = BinaryOp::from(self.previous().ty);
             let rhs = self.term();
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs));
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
+        }
+
+        expr
+    }
+
+    fn term(&mut self) -> Expr {
+        let mut expr = self.factor();
+
+        while self.matches(vec![TokenType::Plus, TokenType::Minus]) {
+            let line = self.previous().line;
+            let operator = BinaryOp::from(self.previous().ty);
+            let rhs = self.factor();
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
         }
 
         expr
     }
 
     fn factor(&mut self) -> Expr {
-        let mut expr = self.unary();
+        let mut expr = self.bit_or();
 
-        while self.matches(vec![TokenType::Star, TokenType::Slash]) {
+        while self.matches(vec![TokenType::Star, TokenType::Slash, TokenType::Percent]) {
+            let line = self.previous().line;
             let operator = BinaryOp::from(self.previous().ty);
-            let rhs = self.unary();
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs));
+            let rhs = self.bit_or();
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
         }
 
         expr
     }
 
-    fn term(&mut self) -> Expr {
-        let mut expr = self.factor();
+    fn bit_or(&mut self) -> Expr {
+        let mut expr = self.bit_xor();
 
-        while self.matches(vec![TokenType::Plus, TokenType::Minus]) {
+        while self.matches(vec![TokenType::Pipe]) {
+            let line = self.previous().line;
             let operator = BinaryOp::from(self.previous().ty);
-            let rhs = self.factor();
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs));
+            let rhs = self.bit_xor();
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
+        }
+
+        expr
+    }
+
+    fn bit_xor(&mut self) -> Expr {
+        let mut expr = self.bit_and();
+
+        while self.matches(vec![TokenType::Caret]) {
+            let line = self.previous().line;
+            let operator = BinaryOp::from(self.previous().ty);
+            let rhs = self.bit_and();
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
+        }
+
+        expr
+    }
+
+    fn bit_and(&mut self) -> Expr {
+        let mut expr = self.shift();
+
+        while self.matches(vec![TokenType::Amp]) {
+            let line = self.previous().line;
+            let operator = BinaryOp::from(self.previous().ty);
+            let rhs = self.shift();
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
+        }
+
+        expr
+    }
+
+    fn shift(&mut self) -> Expr {
+        let mut expr = self.power();
+
+        while self.matches(vec![TokenType::Shl, TokenType::Shr]) {
+            let line = self.previous().line;
+            let operator = BinaryOp::from(self.previous().ty);
+            let rhs = self.power();
+            expr = Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
+        }
+
+        expr
+    }
+
+    fn power(&mut self) -> Expr {
+        let expr = self.unary();
+
+        // Exponentiation is right associative
+        if self.matches(vec![TokenType::StarStar]) {
+            let line = self.previous().line;
+            let operator = BinaryOp::from(self.previous().ty);
+            let rhs = self.power();
+            return Expr::Binary(Box::new(expr), operator, Box::new(rhs), line);
         }
 
         expr
     }
 
     fn unary(&mut self) -> Expr {
-        if self.matches(vec![TokenType::Bang, TokenType::Minus]) {
+        if self.matches(vec![TokenType::Bang, TokenType::Minus, TokenType::Tilde]) {
             let operator = UnaryOp::from(self.previous().ty);
             let rhs = self.unary();
             return Expr::Unary(operator, Box::new(rhs));
         }
 
-        self.atom().unwrap()
+        self.call()
+    }
+
+    fn call(&mut self) -> Expr {
+        let mut expr = self.atom().unwrap();
+
+        loop {
+            if self.matches(vec![TokenType::LParen]) {
+                expr = self.finish_call(expr);
+            } else if self.matches(vec![TokenType::Dot]) {
+                let name = self
+                    .consume(
+                        TokenType::Ident,
+                        "Expected field name after '.'".to_string(),
+                    )
+                    .map(|token| token.lexeme)
+                    .unwrap_or_default();
+                expr = Expr::Get(Box::new(expr), name);
+            } else if self.matches(vec![TokenType::LBracket]) {
+                let index = self.expression();
+                self.consume(TokenType::RBracket, "Expected ']' after index".to_string());
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                break;
+            }
+        }
+
+        expr
+    }
+
+    fn finish_call(&mut self, callee: Expr) -> Expr {
+        let line = self.previous().line;
+        let mut arguments = Vec::new();
+
+        if !self.check(TokenType::RParen) {
+            loop {
+                arguments.push(self.expression());
+
+                if !self.matches(vec![TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(
+            TokenType::RParen,
+            "Expected ')' after arguments".to_string(),
+        );
+
+        Expr::Call(Box::new(callee), arguments, line)
     }
 
     fn atom(&mut self) -> Option<Expr> {
         if self.matches(vec![TokenType::Null]) {
             return Some(Expr::Literal(Literal::Null));
         } else if self.matches(vec![TokenType::Number]) {
-            let literal_value = self.previous().lexeme.parse::<f64>().unwrap();
-            return Some(Expr::Literal(Literal::Number(literal_value)));
+            return Some(Expr::Literal(self.number_literal(&self.previous(), false)));
         } else if self.matches(vec![TokenType::String]) {
             return Some(Expr::Literal(Literal::String(self.previous().lexeme)));
+        } else if self.matches(vec![TokenType::InterpolationStart]) {
+            return Some(self.interpolation());
         } else if self.matches(vec![TokenType::True]) {
             return Some(Expr::Literal(Literal::Bool(true)));
         } else if self.matches(vec![TokenType::False]) {
@@ -273,20 +1582,159 @@ impl<'a> Parser<'a> {
             let character = self.previous().lexeme.parse::<char>().unwrap();
             return Some(Expr::Literal(Literal::Char(character)));
         } else if self.matches(vec![TokenType::Ident]) {
-            return Some(Expr::Variable(self.previous().lexeme));
-        } else if self.matches(vec![TokenType::LParen]) {
-            let expr = self.expression();
+            let name = self.previous().lexeme;
+
+            if self.struct_literals && self.is_struct_literal() {
+                return Some(self.struct_literal(name));
+            }
+
+            return Some(Expr::Variable(name));
+        } else if self.matches(vec![TokenType::Loop]) {
+            return Some(self.loop_expression(None));
+        } else if self.matches(vec![TokenType::Match]) {
+            return Some(self.match_expression());
+        } else if self.matches(vec![TokenType::Fun]) {
+            return Some(self.lambda());
+        } else if self.matches(vec![TokenType::Pipe, TokenType::Or]) {
+            return Some(self.short_lambda());
+        } else if self.check(TokenType::Label) && self.peek_nth(1).ty == TokenType::Colon {
+            let label = self.advance().lexeme;
+            self.advance();
+
+            self.consume(TokenType::Loop, "Expected 'loop' after label".to_string());
+            return Some(self.loop_expression(Some(label)));
+        } else if self.matches(vec![TokenType::SSelf]) {
+            if self.method_depth == 0 {
+                self.report(self.previous(), "'self' outside of method".to_string());
+            }
+
+            return Some(Expr::Variable("self".to_string()));
+        } else if self.matches(vec![TokenType::LBracket]) {
+            let mut elements = Vec::new();
+
+            if !self.check(TokenType::RBracket) {
+                loop {
+                    elements.push(self.expression());
+
+                    if !self.matches(vec![TokenType::Comma]) || self.check(TokenType::RBracket) {
+                        break;
+                    }
+                }
+            }
+
             self.consume(
-                TokenType::RParen,
-                "Expected ')' after expression".to_string(),
+                TokenType::RBracket,
+                "Expected ']' after list elements".to_string(),
             );
-            return Some(Expr::Grouping(Box::new(expr)));
+            return Some(Expr::List(elements));
+        } else if self.matches(vec![TokenType::LCurly]) {
+            let mut entries = Vec::new();
+
+            if !self.check(TokenType::RCurly) {
+                loop {
+                    let key = self.expression();
+                    self.consume(TokenType::Colon, "Expected ':' after map key".to_string());
+                    entries.push((key, self.expression()));
+
+                    if !self.matches(vec![TokenType::Comma]) || self.check(TokenType::RCurly) {
+                        break;
+                    }
+                }
+            }
+
+            self.consume(
+                TokenType::RCurly,
+                "Expected '}' after map entries".to_string(),
+            );
+            return Some(Expr::Map(entries));
+        } else if self.matches(vec![TokenType::LParen]) {
+            let struct_literals = std::mem::replace(&mut self.struct_literals, true);
+            let expr = self.grouping();
+            self.struct_literals = struct_literals;
+
+            return Some(expr);
         }
 
         self.error(self.peek(), "Expected expression".to_string());
         None
     }
 
+    fn grouping(&mut self) -> Expr {
+        if self.matches(vec![TokenType::RParen]) {
+            return Expr::Tuple(Vec::new());
+        }
+
+        let expr = self.expression();
+
+        if self.matches(vec![TokenType::Comma]) {
+            let mut elements = vec![expr];
+
+            while !self.check(TokenType::RParen) && !self.is_at_end() {
+                elements.push(self.expression());
+
+                if !self.matches(vec![TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(
+                TokenType::RParen,
+                "Expected ')' after tuple elements".to_string(),
+            );
+            return Expr::Tuple(elements);
+        }
+
+        self.consume(
+            TokenType::RParen,
+            "Expected ')' after expression".to_string(),
+        );
+        Expr::Grouping(Box::new(expr))
+    }
+
+    fn is_struct_literal(&self) -> bool {
+        if !self.check(TokenType::LCurly) {
+            return false;
+        }
+
+        match self.peek_nth(1).ty {
+            TokenType::RCurly => true,
+            TokenType::Ident => self.peek_nth(2).ty == TokenType::Colon,
+            _ => false,
+        }
+    }
+
+    fn struct_literal(&mut self, name: String) -> Expr {
+        self.consume(
+            TokenType::LCurly,
+            "Expected '{' after struct name".to_string(),
+        );
+
+        let mut fields = Vec::new();
+        if !self.check(TokenType::RCurly) {
+            while let Some(field) =
+                self.consume(TokenType::Ident, "Expected field name".to_string())
+            {
+                self.consume(
+                    TokenType::Colon,
+                    "Expected ':' after field name".to_string(),
+                );
+
+                fields.push((field.lexeme, self.expression()));
+
+                if !self.matches(vec![TokenType::Comma]) || self.check(TokenType::RCurly) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(
+            TokenType::RCurly,
+            "Expected '}' after struct fields".to_string(),
+        );
+
+        Expr::Construct(name, fields)
+    }
+
     fn synchronize(&mut self) {
         self.advance();
 
@@ -297,9 +1745,20 @@ impl<'a> Parser<'a> {
 
             type TT = TokenType;
             match self.peek().ty {
-                TT::Struct | TT::Fun | TT::Let | TT::For | TT::If | TT::While | TT::Return => {
-                    return
-                }
+                TT::Struct
+                | TT::Enum
+                | TT::Fun
+                | TT::Let
+                | TT::Const
+                | TT::For
+                | TT::If
+                | TT::While
+                | TT::Loop
+                | TT::Match
+                | TT::Import
+                | TT::Try
+                | TT::Throw
+                | TT::Return => return,
                 _ => (),
             }
 
@@ -321,10 +1780,24 @@ impl<'a> Parser<'a> {
     }
 
     fn error(&mut self, token: Token, msg: String) {
-        crate::error(token.line, format!("at '{}', {msg}", token.lexeme));
+        self.report(token, msg);
         self.synchronize();
     }
 
+    fn report(&self, token: Token, msg: String) {
+        crate::error(token.line, format!("at '{}', {msg}", token.lexeme));
+        crate::set_error_found(true);
+    }
+
+    fn matches_word(&mut self, word: &str) -> bool {
+        if self.check(TokenType::Ident) && self.peek().lexeme == word {
+            self.advance();
+            return true;
+        }
+
+        false
+    }
+
     fn matches(&mut self, types: Vec<TokenType>) -> bool {
         for ty in types.iter() {
             if self.check(*ty) {
@@ -0,0 +1,105 @@
+use super::{Expr, Literal, Stmt};
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Wildcard,
+    Binding(String),
+    Literal(Literal),
+    Range(Literal, Literal, bool),
+    Or(Vec<Pattern>),
+    Tuple(Vec<Pattern>),
+    List(Vec<Pattern>, Option<String>),
+    Variant(Vec<String>, String, Option<Vec<Pattern>>),
+}
+
+#[derive(Debug, Clone)]
+pub enum ArmBody {
+    Expr(Expr),
+    Block(Vec<Stmt>),
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: ArmBody,
+}
+
+impl Pattern {
+    pub fn bindings(&self) -> Vec<String> {
+        match self {
+            Pattern::Wildcard | Pattern::Literal(..) | Pattern::Range(..) => Vec::new(),
+            Pattern::Binding(name) => vec![name.clone()],
+            Pattern::Or(alternatives) => alternatives
+                .first()
+                .map(|p| p.bindings())
+                .unwrap_or_default(),
+            Pattern::Tuple(elements) => elements.iter().flat_map(|p| p.bindings()).collect(),
+            Pattern::Variant(_, _, payload) => payload
+                .iter()
+                .flatten()
+                .flat_map(|p| p.bindings())
+                .collect(),
+            Pattern::List(elements, rest) => {
+                let mut names: Vec<String> = elements.iter().flat_map(|p| p.bindings()).collect();
+                names.extend(rest.clone());
+                names
+            }
+        }
+    }
+}
+
+fn write_separated(
+    f: &mut std::fmt::Formatter<'_>,
+    patterns: &[Pattern],
+    separator: &str,
+) -> std::fmt::Result {
+    for (i, pattern) in patterns.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{separator}")?;
+        }
+        write!(f, "{pattern}")?;
+    }
+
+    Ok(())
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Binding(name) => write!(f, "{name}"),
+            Pattern::Literal(literal) => write!(f, "{literal}"),
+            Pattern::Range(start, end, inclusive) => {
+                let op = if *inclusive { "..=" } else { ".." };
+                write!(f, "{start}{op}{end}")
+            }
+            Pattern::Or(alternatives) => write_separated(f, alternatives, " | "),
+            Pattern::Tuple(elements) => {
+                write!(f, "(")?;
+                write_separated(f, elements, ", ")?;
+                write!(f, ")")
+            }
+            Pattern::List(elements, rest) => {
+                write!(f, "[")?;
+                write_separated(f, elements, ", ")?;
+                if let Some(rest) = rest {
+                    if !elements.is_empty() {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{rest}...")?;
+                }
+                write!(f, "]")
+            }
+            Pattern::Variant(path, variant, payload) => {
+                write!(f, "{}.{variant}", path.join("."))?;
+                if let Some(payload) = payload {
+                    write!(f, "(")?;
+                    write_separated(f, payload, ", ")?;
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+    pub step: i64,
+    pub inclusive: bool,
+}
+
+impl Range {
+    pub fn new(start: i64, end: i64, inclusive: bool) -> Self {
+        Self {
+            start,
+            end,
+            step: 1,
+            inclusive,
+        }
+    }
+
+    pub fn with_step(self, step: i64) -> Self {
+        Self { step, ..self }
+    }
+
+    pub fn iter(&self) -> RangeIter {
+        RangeIter {
+            range: *self,
+            next: Some(self.start),
+        }
+    }
+}
+
+pub struct RangeIter {
+    range: Range,
+    next: Option<i64>,
+}
+
+impl Iterator for RangeIter {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let current = self.next?;
+        let Range {
+            end,
+            step,
+            inclusive,
+            ..
+        } = self.range;
+
+        let in_bounds = match (step > 0, inclusive) {
+            (true, true) => current <= end,
+            (true, false) => current < end,
+            (false, true) => current >= end,
+            (false, false) => current > end,
+        };
+
+        if !in_bounds {
+            self.next = None;
+            return None;
+        }
+
+        self.next = current.checked_add(step);
+        Some(current)
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = if self.inclusive { "..=" } else { ".." };
+
+        if self.step == 1 {
+            write!(f, "{}{op}{}", self.start, self.end)
+        } else {
+            write!(f, "({}{op}{}).step({})", self.start, self.end, self.step)
+        }
+    }
+}
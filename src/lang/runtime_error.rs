@@ -1,9 +1,24 @@
+use super::Literal;
+
+#[derive(Debug)]
 pub enum ErrorKind {
     OperatorNotDefined,
     ZeroDivision,
+    IntegerOverflow,
     TypeMismatch,
     UninitializedAccess,
     UndefinedVariable,
+    ArityMismatch,
+    ConstantReassignment,
+    UndefinedField,
+    IndexOutOfBounds,
+    KeyNotFound,
+    PatternMismatch,
+    InvalidArgument,
+    ImportError,
+    StackOverflow,
+    Thrown,
+    Return,
 }
 
 impl std::fmt::Display for ErrorKind {
@@ -11,9 +26,21 @@ impl std::fmt::Display for ErrorKind {
         match self {
             ErrorKind::OperatorNotDefined => write!(f, "Operator not defined"),
             ErrorKind::ZeroDivision => write!(f, "Division by zero"),
+            ErrorKind::IntegerOverflow => write!(f, "Integer overflow"),
             ErrorKind::TypeMismatch => write!(f, "Type mismatch"),
             ErrorKind::UninitializedAccess => write!(f, "Uninitialized access"),
             ErrorKind::UndefinedVariable => write!(f, "Undefined variable"),
+            ErrorKind::ArityMismatch => write!(f, "Arity mismatch"),
+            ErrorKind::ConstantReassignment => write!(f, "Constant reassignment"),
+            ErrorKind::UndefinedField => write!(f, "Undefined field"),
+            ErrorKind::IndexOutOfBounds => write!(f, "Index out of bounds"),
+            ErrorKind::KeyNotFound => write!(f, "Key not found"),
+            ErrorKind::PatternMismatch => write!(f, "Pattern mismatch"),
+            ErrorKind::InvalidArgument => write!(f, "Invalid argument"),
+            ErrorKind::ImportError => write!(f, "Import error"),
+            ErrorKind::StackOverflow => write!(f, "Stack overflow"),
+            ErrorKind::Thrown => write!(f, "Uncaught exception"),
+            ErrorKind::Return => write!(f, "Return outside of function"),
         }
     }
 }
@@ -21,6 +48,27 @@ impl std::fmt::Display for ErrorKind {
 pub struct RuntimeError {
     pub what: String,
     pub kind: ErrorKind,
+    pub value: Option<Literal>,
+}
+
+impl RuntimeError {
+    pub fn thrown(value: Literal) -> Self {
+        Self {
+            what: value.to_string(),
+            kind: ErrorKind::Thrown,
+            value: Some(value),
+        }
+    }
+
+    /// Carries a `return` out of an expression, the enclosing statement turns
+    /// it back into a return.
+    pub fn returning(value: Literal) -> Self {
+        Self {
+            what: value.to_string(),
+            kind: ErrorKind::Return,
+            value: Some(value),
+        }
+    }
 }
 
 impl std::fmt::Display for RuntimeError {
@@ -1,10 +1,25 @@
-use super::Expr;
+use super::{Expr, Import, MatchArm, Pattern, Signature, TypeAnnotation};
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub enum Stmt {
     Expr(Box<Expr>),
     If(Box<Expr>, Box<Stmt>, Option<Box<Stmt>>),
-    Let(String, Option<Box<Expr>>),
+    Let(String, Option<Box<Expr>>, Option<TypeAnnotation>),
+    Destructure(Pattern, Box<Expr>),
+    Const(String, Box<Expr>, Option<TypeAnnotation>),
     Block(Vec<Stmt>),
     Print(Box<Expr>),
+    Function(String, Vec<String>, Vec<Stmt>, Signature),
+    Return(Option<Box<Expr>>, u32),
+    Struct(String, Vec<String>, Vec<Stmt>),
+    Enum(String, Vec<(String, Vec<String>)>),
+    While(Box<Expr>, Box<Stmt>, Option<Box<Expr>>, Option<String>),
+    Loop(Box<Stmt>, Option<String>),
+    ForIn(Pattern, Box<Expr>, Box<Stmt>, Option<String>),
+    Break(Option<String>, Option<Box<Expr>>),
+    Continue(Option<String>),
+    Match(Box<Expr>, Vec<MatchArm>),
+    Import(String, Import),
+    Try(Vec<Stmt>, Option<(String, Vec<Stmt>)>, Option<Vec<Stmt>>),
+    Throw(Box<Expr>),
 }
@@ -0,0 +1,57 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use super::{Function, Literal};
+
+#[derive(Debug)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Vec<String>,
+    pub methods: HashMap<String, Rc<Function>>,
+}
+
+impl Struct {
+    pub fn new(name: String, fields: Vec<String>, methods: HashMap<String, Rc<Function>>) -> Self {
+        Self {
+            name,
+            fields,
+            methods,
+        }
+    }
+
+    pub fn has_field(&self, name: &String) -> bool {
+        self.fields.contains(name)
+    }
+}
+
+#[derive(Debug)]
+pub struct Instance {
+    pub structure: Rc<Struct>,
+    pub fields: HashMap<String, Literal>,
+}
+
+impl Instance {
+    pub fn new(structure: Rc<Struct>, fields: HashMap<String, Literal>) -> Self {
+        Self { structure, fields }
+    }
+
+    pub fn get(instance: &Rc<RefCell<Instance>>, name: &String) -> Option<Literal> {
+        if let Some(value) = instance.borrow().fields.get(name) {
+            return Some(value.clone());
+        }
+
+        let method = instance.borrow().structure.methods.get(name).cloned();
+        method.map(|method| {
+            let bound = method.bind(Literal::Instance(instance.clone()));
+            Literal::Function(Rc::new(bound))
+        })
+    }
+
+    pub fn set(&mut self, name: String, value: Literal) -> bool {
+        if !self.structure.has_field(&name) {
+            return false;
+        }
+
+        self.fields.insert(name, value);
+        true
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::Display;
+
+use super::Literal;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Any,
+    Int,
+    Float,
+    Number,
+    Bool,
+    Char,
+    String,
+    List,
+    Tuple,
+    Map,
+    Range,
+    Function,
+    Module,
+    Null,
+    Named(String),
+}
+
+impl Type {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "any" => Type::Any,
+            "int" => Type::Int,
+            "float" => Type::Float,
+            "number" => Type::Number,
+            "bool" => Type::Bool,
+            "char" => Type::Char,
+            "string" => Type::String,
+            "list" => Type::List,
+            "tuple" => Type::Tuple,
+            "map" => Type::Map,
+            "range" => Type::Range,
+            "function" => Type::Function,
+            "module" => Type::Module,
+            "null" => Type::Null,
+            _ => Type::Named(name.to_string()),
+        }
+    }
+
+    pub fn of(literal: &Literal) -> Self {
+        match literal {
+            Literal::Int(..) => Type::Int,
+            Literal::Number(..) => Type::Float,
+            Literal::String(..) => Type::String,
+            Literal::Bool(..) => Type::Bool,
+            Literal::Char(..) => Type::Char,
+            Literal::Function(..) | Literal::NativeMethod(..) => Type::Function,
+            Literal::Struct(..) => Type::Any,
+            Literal::Instance(x) => Type::Named(x.borrow().structure.name.clone()),
+            Literal::Enum(..) => Type::Any,
+            Literal::Variant(x) => Type::Named(x.enumeration.name.clone()),
+            Literal::List(..) => Type::List,
+            Literal::Tuple(..) => Type::Tuple,
+            Literal::Range(..) => Type::Range,
+            Literal::Map(..) => Type::Map,
+            Literal::Module(..) => Type::Module,
+            Literal::Null => Type::Null,
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Float | Type::Number)
+    }
+
+    // Unknown types are accepted everywhere, so unannotated code is never rejected.
+    // `float` doesn't accept `int`: an int value stays an int at runtime and keeps
+    // integer division, so float bindings need a float literal such as `1.0`
+    pub fn accepts(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Type::Any, _) | (_, Type::Any) => true,
+            (Type::Number, other) => other.is_numeric(),
+            (Type::Int | Type::Float, Type::Number) => true,
+            _ => self == other,
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Any => write!(f, "any"),
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::Number => write!(f, "number"),
+            Type::Bool => write!(f, "bool"),
+            Type::Char => write!(f, "char"),
+            Type::String => write!(f, "string"),
+            Type::List => write!(f, "list"),
+            Type::Tuple => write!(f, "tuple"),
+            Type::Map => write!(f, "map"),
+            Type::Range => write!(f, "range"),
+            Type::Function => write!(f, "function"),
+            Type::Module => write!(f, "module"),
+            Type::Null => write!(f, "null"),
+            Type::Named(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TypeAnnotation {
+    pub ty: Type,
+    pub line: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Signature {
+    pub params: Vec<Option<TypeAnnotation>>,
+    pub ret: Option<TypeAnnotation>,
+}
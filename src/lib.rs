@@ -6,40 +6,59 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub struct Stellar {
     interpreter: lang::Interpreter,
+    checker: lang::Checker,
 }
 
 impl Stellar {
     pub fn new(properties: lang::InterpreterProperties) -> Self {
         Self {
             interpreter: lang::Interpreter::new(properties),
+            checker: lang::Checker::new(),
         }
     }
 
-    pub fn run(&mut self, source: String) {
+    pub fn run(&mut self, source: String) -> bool {
         let mut lexer = lang::Lexer::new(source);
         let tokens = lexer.scan_tokens();
 
         if tokens.len() == 1 {
-            return;
+            return true;
         }
 
         if self.handle_error_runtime() {
-            return;
+            return false;
         }
 
         let mut parser = lang::Parser::new(tokens);
         let statements = parser.parse();
 
         if self.handle_error_runtime() {
-            return;
+            return false;
+        }
+
+        self.checker.check(&statements);
+
+        if self.handle_error_runtime() {
+            return false;
         }
 
-        self.interpreter.interpret(&statements);
+        if let Err(err) = self.interpreter.interpret(&statements) {
+            println!("Runtime Error: {err}");
+            return false;
+        }
+
+        true
     }
 
-    pub fn run_file(&mut self, path: String) {
-        if let Ok(source) = std::fs::read_to_string(path) {
-            self.run(source);
+    pub fn run_file(&mut self, path: String) -> bool {
+        self.interpreter.enter_script(std::path::Path::new(&path));
+
+        match std::fs::read_to_string(&path) {
+            Ok(source) => self.run(source),
+            Err(err) => {
+                println!("Error: cannot read '{path}': {err}");
+                false
+            }
         }
     }
 
@@ -50,8 +69,12 @@ impl Stellar {
             print!(">> ");
             std::io::stdout().flush().unwrap();
             let mut line = String::new();
-            std::io::stdin().read_line(&mut line).unwrap();
+            if std::io::stdin().read_line(&mut line).unwrap() == 0 {
+                println!();
+                break;
+            }
 
+            // Errors only abort the current line, the session keeps its state
             self.run(line);
 
             if crate::error_found() {
@@ -89,13 +112,7 @@ pub fn set_error_found(err: bool) {
 }
 
 pub fn print_literal(literal: lang::Literal) {
-    match literal {
-        lang::Literal::Number(x) => println!("{x}"),
-        lang::Literal::String(x) => println!("{x}"),
-        lang::Literal::Bool(x) => println!("{x}"),
-        lang::Literal::Char(x) => println!("{x}"),
-        lang::Literal::Null => println!("null"),
-    }
+    println!("{literal}");
 }
 
 pub fn print_welcome_msg() {
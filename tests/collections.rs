@@ -0,0 +1,127 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn list_index_reads_and_writes() {
+    let source = "
+        let xs = [1, 2, 3];
+        print xs[0];
+        print xs[2];
+        xs[0] = 9;
+        print xs;
+        print [1, [2, 3]][1][0];
+    ";
+    assert_eq!(run(source), "1\n3\n[9, 2, 3]\n2\n");
+}
+
+#[test]
+fn lists_are_shared_by_reference() {
+    let source = "
+        let xs = [1, 2];
+        let ys = xs;
+        ys[0] = 5;
+        print xs;
+    ";
+    assert_eq!(run(source), "[5, 2]\n");
+}
+
+#[test]
+fn index_past_the_end_is_out_of_bounds() {
+    let output = run("let xs = [1, 2, 3]; print xs[3];");
+    assert!(output.starts_with("Runtime Error: Index out of bounds: index 3 is out of bounds"));
+}
+
+#[test]
+fn negative_index_is_out_of_bounds() {
+    let output = run("let xs = [1, 2, 3]; xs[-1] = 0;");
+    assert!(output.starts_with("Runtime Error: Index out of bounds: index -1 is out of bounds"));
+}
+
+#[test]
+fn map_literal_reads_and_writes() {
+    let source = "
+        let m = {\"a\": 1, 2: \"two\", true: [1]};
+        print m;
+        print m[\"a\"];
+        print m[2];
+        m[\"a\"] = 5;
+        m[\"b\"] = 6;
+        print m;
+        print {};
+    ";
+    assert_eq!(
+        run(source),
+        "{\"a\": 1, 2: \"two\", true: [1]}\n1\ntwo\n{\"a\": 5, 2: \"two\", true: [1], \"b\": 6}\n{}\n"
+    );
+}
+
+#[test]
+fn map_methods_keep_insertion_order() {
+    let source = "
+        let m = {\"a\": 1, \"b\": 2, \"c\": 3, \"d\": 4};
+        print m.has(\"b\");
+        print m.remove(\"b\");
+        print m.has(\"b\");
+        print m.remove(\"z\");
+        print m.keys();
+        m[\"b\"] = 5;
+        print m;
+        print m[\"d\"];
+    ";
+    assert_eq!(
+        run(source),
+        "true\n2\nfalse\nnull\n[\"a\", \"c\", \"d\"]\n{\"a\": 1, \"c\": 3, \"d\": 4, \"b\": 5}\n4\n"
+    );
+}
+
+#[test]
+fn missing_map_key_is_an_error() {
+    assert_eq!(
+        run("let m = {\"a\": 1}; print m[\"z\"];"),
+        "Runtime Error: Key not found: key 'z' is not in map\n"
+    );
+}
+
+#[test]
+fn unhashable_map_key_is_an_error() {
+    assert_eq!(
+        run("let m = {[1]: 2};"),
+        "Runtime Error: Type mismatch: type 'list' cannot be used as a map key\n"
+    );
+}
+
+#[test]
+fn maps_compare_by_entries() {
+    let source = "
+        print {\"a\": 1, \"b\": [2]} == {\"b\": [2], \"a\": 1};
+        print {\"a\": 1} == {\"a\": 2};
+        print {\"a\": 1} != {\"a\": 1, \"b\": 2};
+    ";
+    assert_eq!(run(source), "true\nfalse\ntrue\n");
+}
+
+#[test]
+fn integral_float_key_finds_int_entry() {
+    let source = "
+        let m = {1: \"int\"};
+        print m[1.0];
+        m[1.0] = \"float\";
+        print m;
+        print {1: \"x\"} == {1.0: \"x\"};
+    ";
+    assert_eq!(run(source), "int\n{1: \"float\"}\ntrue\n");
+}
+
+#[test]
+fn entries_after_a_removal_are_updated_in_place() {
+    let source = "
+        let m = {1: \"a\", 2: \"b\", 3: \"c\"};
+        m.remove(1);
+        m[3] = \"z\";
+        m[2.0] = \"y\";
+        print m;
+        print m.keys();
+    ";
+    assert_eq!(run(source), "{2: \"y\", 3: \"z\"}\n[2, 3]\n");
+}
@@ -0,0 +1,69 @@
+//! Helpers shared by the integration tests, which drive the `testbed` binary.
+#![allow(dead_code)]
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SCRIPTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs `source` as a script and returns its stdout.
+pub fn run(source: &str) -> String {
+    stdout(&run_output(source))
+}
+
+/// Runs `source` as a script and returns the whole process output.
+pub fn run_output(source: &str) -> Output {
+    // Tests run in parallel, and so do the test binaries, so every script
+    // gets its own file
+    let script = SCRIPTS.fetch_add(1, Ordering::Relaxed);
+    let path =
+        std::env::temp_dir().join(format!("stellar_test_{}_{script}.st", std::process::id()));
+    std::fs::write(&path, source).unwrap();
+
+    let output = run_file(&path);
+
+    std::fs::remove_file(&path).unwrap();
+    output
+}
+
+/// Runs the script at `tests/fixtures/{fixture}`.
+pub fn run_fixture(fixture: &str) -> Output {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(fixture);
+
+    run_file(&path)
+}
+
+pub fn run_file(path: &Path) -> Output {
+    // Imports resolve against the importing file, not the working directory
+    Command::new(env!("CARGO_BIN_EXE_testbed"))
+        .arg(path)
+        .current_dir(std::env::temp_dir())
+        .output()
+        .unwrap()
+}
+
+/// Feeds `input` to a REPL session and returns its stdout.
+pub fn repl(input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_testbed"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    stdout(&child.wait_with_output().unwrap())
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
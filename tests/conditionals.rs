@@ -0,0 +1,40 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn else_if_chain_takes_first_true_branch() {
+    let source = "
+        fun grade(n) {
+            if (n > 90) {
+                return \"a\";
+            } else if (n > 80) {
+                return \"b\";
+            } else if (n > 70) {
+                return \"c\";
+            } else {
+                return \"f\";
+            }
+        }
+        print grade(95);
+        print grade(85);
+        print grade(75);
+        print grade(10);
+    ";
+    assert_eq!(run(source), "a\nb\nc\nf\n");
+}
+
+#[test]
+fn else_if_chain_without_else_can_skip_every_branch() {
+    let source = "
+        if (false) { print 1; } else if (false) { print 2; }
+        print 3;
+    ";
+    assert_eq!(run(source), "3\n");
+}
+
+#[test]
+fn branch_without_braces_is_rejected() {
+    let output = run("if (true) print 1;");
+    assert!(output.starts_with("[Line: 1] Error: at 'print', Expected '{' after condition"));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn tuple_destructuring_binds_each_element() {
+    let source = "
+        let (a, b) = (1, \"x\");
+        print a;
+        print b;
+        let (c, _) = (3, 4);
+        print c;
+        print (1,);
+        print ();
+    ";
+    assert_eq!(run(source), "1\nx\n3\n(1,)\n()\n");
+}
+
+#[test]
+fn list_destructuring_binds_rest() {
+    let source = "
+        let [x, y, rest...] = [1, 2, 3, 4];
+        print x;
+        print y;
+        print rest;
+    ";
+    assert_eq!(run(source), "1\n2\n[3, 4]\n");
+}
+
+#[test]
+fn nested_patterns_destructure() {
+    let source = "
+        let ((a, b), [c, d]) = ((1, 2), [3, 4]);
+        print a + b + c + d;
+    ";
+    assert_eq!(run(source), "10\n");
+}
+
+#[test]
+fn arity_mismatch_is_a_pattern_mismatch() {
+    assert_eq!(
+        run("let (a, b) = (1, 2, 3);"),
+        "Runtime Error: Pattern mismatch: cannot destructure '(1, 2, 3)' with pattern '(a, b)'\n"
+    );
+    assert_eq!(
+        run("let [a, b] = [1];"),
+        "Runtime Error: Pattern mismatch: cannot destructure '[1]' with pattern '[a, b]'\n"
+    );
+}
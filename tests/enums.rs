@@ -0,0 +1,67 @@
+mod common;
+
+use common::{run, run_fixture, run_output, stdout};
+
+#[test]
+fn variant_pattern_binds_payload() {
+    let source = "
+        enum Shape { Circle(radius), Empty }
+        match Shape.Circle(2) {
+            Shape.Circle(r) => { print r; },
+            Shape.Empty => { print \"empty\"; },
+        }
+    ";
+    assert_eq!(run(source), "2\n");
+}
+
+#[test]
+fn qualified_variant_pattern_resolves_through_module() {
+    let output = run_fixture("enums/qualified.st");
+    assert_eq!(stdout(&output), "30\n3\n");
+}
+
+#[test]
+fn variant_pattern_with_shadowed_enum_is_an_error() {
+    let source = "
+        enum Shape { Circle(radius), Empty }
+        fun radius(Shape) {
+            return match (Shape) { Shape.Circle(r) => r, _ => 0 };
+        }
+        print radius(Shape.Circle(1));
+    ";
+    assert!(run(source).contains("'Shape' in pattern is not an enum\n"));
+}
+
+#[test]
+fn variant_pattern_with_unknown_variant_is_an_error() {
+    let source = "
+        enum Shape { Circle(radius), Empty }
+        match (Shape.Empty) { Shape.Triangle => { print 1; }, _ => { print 2; } }
+    ";
+    assert!(run(source).contains("enum 'Shape' has no variant 'Triangle'\n"));
+}
+
+#[test]
+fn variant_name_is_read_through_variant_field() {
+    let source = "
+        enum Shape { Circle(radius), Empty }
+        let c = Shape.Circle(2);
+        print c.variant;
+        print c.radius;
+    ";
+    assert_eq!(run(source), "Circle\n2\n");
+}
+
+#[test]
+fn payload_field_named_variant_is_rejected() {
+    let output = run("enum Tag { Named(variant) }");
+    assert!(output.contains("'variant' can't be used as a variant field name"));
+}
+
+#[test]
+fn invalid_enum_name_is_reported_without_panicking() {
+    let output = run_output("enum 3 {}");
+
+    assert!(stdout(&output).starts_with("[Line: 1] Error: at '3', Expected enum name"));
+    assert!(output.stderr.is_empty());
+}
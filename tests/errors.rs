@@ -0,0 +1,55 @@
+mod common;
+
+use std::path::Path;
+
+use common::{run_file, run_output, stdout};
+
+#[test]
+fn uncaught_error_exits_with_failure() {
+    let output = run_output("print 1; print 1 / 0; print 2;");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "1\nRuntime Error: Division by zero: cannot divide by zero\n"
+    );
+}
+
+#[test]
+fn caught_error_exits_with_success() {
+    let output = run_output("try { print 1 / 0; } catch (e) { print 1; }");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "1\n");
+}
+
+#[test]
+fn missing_script_exits_with_failure() {
+    let output = run_file(Path::new("stellar_errors_missing.st"));
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn unbounded_recursion_raises_catchable_error() {
+    let source = "
+        fun f(n) { return f(n + 1); }
+        try { f(0); } catch (e) { print e.kind; }
+        print \"after\";
+    ";
+    let output = run_output(source);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "StackOverflow\nafter\n");
+}
+
+#[test]
+fn recursion_below_call_depth_limit_succeeds() {
+    let source = "
+        fun f(n) { if (n == 0) { return 0; } return f(n - 1) + 1; }
+        print f(999);
+    ";
+    let output = run_output(source);
+
+    assert_eq!(stdout(&output), "999\n");
+}
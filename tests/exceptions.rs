@@ -0,0 +1,65 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn runtime_error_is_caught_with_kind_and_what() {
+    let source = "
+        try { print 1 / 0; } catch (e) { print e.kind; print e.what; }
+        print \"after\";
+    ";
+    assert_eq!(run(source), "ZeroDivision\ncannot divide by zero\nafter\n");
+}
+
+#[test]
+fn thrown_value_is_caught_as_is() {
+    let source = "try { throw [1, 2]; } catch (e) { print e[1]; }";
+    assert_eq!(run(source), "2\n");
+}
+
+#[test]
+fn finally_runs_on_normal_path() {
+    let source = "
+        try { print \"body\"; } catch (e) { print \"catch\"; } finally { print \"finally\"; }
+    ";
+    assert_eq!(run(source), "body\nfinally\n");
+}
+
+#[test]
+fn finally_runs_on_error_path() {
+    let source = "
+        fun f() {
+            try { throw \"boom\"; } finally { print \"finally\"; }
+        }
+        try { f(); } catch (e) { print e; }
+    ";
+    assert_eq!(run(source), "finally\nboom\n");
+}
+
+#[test]
+fn finally_runs_on_return() {
+    let source = "
+        fun f() {
+            try { return 1; } finally { print \"finally\"; }
+        }
+        print f();
+    ";
+    assert_eq!(run(source), "finally\n1\n");
+}
+
+#[test]
+fn caught_error_can_be_rethrown() {
+    let source = "
+        try {
+            try { print 1 / 0; } catch (e) { print \"inner\"; throw e; }
+        } catch (e) {
+            print e.kind;
+        }
+        try {
+            try { throw \"a\"; } catch (e) { throw e + \"b\"; }
+        } catch (e) {
+            print e;
+        }
+    ";
+    assert_eq!(run(source), "inner\nZeroDivision\nab\n");
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::{run_fixture, stdout};
+
+#[test]
+fn module_runs_once_and_is_shared_between_imports() {
+    let output = run_fixture("imports/caching.st");
+    assert_eq!(stdout(&output), "loading counter\n1\n2\n2\n");
+}
+
+#[test]
+fn module_is_bound_under_its_alias() {
+    let output = run_fixture("imports/aliasing.st");
+    assert_eq!(stdout(&output), "loading counter\ncounter\n1\n");
+}
+
+#[test]
+fn selective_import_binds_names_and_aliases() {
+    let output = run_fixture("imports/selective.st");
+    assert_eq!(stdout(&output), "loading counter\ncounter\n1\n2\n");
+}
+
+#[test]
+fn selective_import_of_missing_name_fails() {
+    let output = run_fixture("imports/missing_name.st");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("module 'counter' has no binding 'missing'"));
+}
+
+#[test]
+fn imports_resolve_relative_to_importing_file() {
+    let output = run_fixture("imports/relative.st");
+    assert_eq!(stdout(&output), "hello world!\n");
+}
+
+#[test]
+fn import_cycle_is_reported() {
+    let output = run_fixture("imports/cycle_a.st");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("import cycle detected"));
+    assert!(stdout(&output).contains("cycle_b.st"));
+}
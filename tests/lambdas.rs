@@ -0,0 +1,47 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn anonymous_functions_can_be_called() {
+    let source = "
+        let add = fun(a, b) { return a + b; };
+        print add(1, 2);
+        let square = |x| x * x;
+        print square(4);
+        let seven = || 7;
+        print seven();
+    ";
+    assert_eq!(run(source), "3\n16\n7\n");
+}
+
+#[test]
+fn lambdas_are_passed_and_returned() {
+    let source = "
+        fun apply(f, v) { return f(v); }
+        print apply(|x| x * 3, 5);
+        let compose = |f, g| |x| f(g(x));
+        print compose(|x| x + 1, |x| x * 2)(5);
+    ";
+    assert_eq!(run(source), "15\n11\n");
+}
+
+#[test]
+fn lambdas_capture_variables() {
+    let source = "
+        let base = 10;
+        let add = |x| x + base;
+        base = 20;
+        print add(1);
+        fun counter() {
+            let n = 0;
+            return fun() { n += 1; return n; };
+        }
+        let c = counter();
+        c();
+        c();
+        print c();
+        print counter()();
+    ";
+    assert_eq!(run(source), "21\n3\n1\n");
+}
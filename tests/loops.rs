@@ -0,0 +1,199 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn while_loop_runs_until_condition_is_false() {
+    let source = "
+        let i = 0;
+        while ((i = i + 1) < 4) {
+            print i;
+        }
+        print i;
+    ";
+    assert_eq!(run(source), "1\n2\n3\n4\n");
+}
+
+#[test]
+fn while_loop_with_false_condition_never_runs() {
+    let source = "
+        let x = 1;
+        while (false) { x = 2; }
+        print x;
+    ";
+    assert_eq!(run(source), "1\n");
+}
+
+#[test]
+fn for_loop_variable_is_scoped_to_the_loop() {
+    let source = "
+        let i = 10;
+        for (let i = 0; (i = i + 1) < 3;) {
+            print i;
+        }
+        print i;
+    ";
+    assert_eq!(run(source), "1\n2\n10\n");
+}
+
+#[test]
+fn for_loop_clauses_are_optional() {
+    let source = "
+        for (; false;) {
+            print \"never\";
+        }
+        print \"done\";
+    ";
+    assert_eq!(run(source), "done\n");
+}
+
+#[test]
+fn while_loop_counts() {
+    let source = "
+        let i = 0;
+        let sum = 0;
+        while (i < 5) {
+            sum = sum + i;
+            i = i + 1;
+        }
+        print i;
+        print sum;
+    ";
+    assert_eq!(run(source), "5\n10\n");
+}
+
+#[test]
+fn for_loop_increment_persists() {
+    let source = "
+        let i = 10;
+        let sum = 0;
+        for (let i = 0; i < 4; i = i + 1) {
+            sum = sum + i;
+        }
+        print sum;
+        print i;
+    ";
+    assert_eq!(run(source), "6\n10\n");
+}
+
+#[test]
+fn continue_in_for_loop_runs_increment() {
+    let source = "
+        for (let i = 0; i < 5; i = i + 1) {
+            if (i == 1 || i == 3) { continue; }
+            print i;
+        }
+    ";
+    assert_eq!(run(source), "0\n2\n4\n");
+}
+
+#[test]
+fn loop_breaks_with_value() {
+    let source = "
+        let i = 0;
+        let found = loop {
+            i += 1;
+            if (i == 4) { break i * 10; }
+        };
+        print found;
+        print loop { break; };
+    ";
+    assert_eq!(run(source), "40\nnull\n");
+}
+
+#[test]
+fn labeled_break_and_continue_target_outer_loop() {
+    let source = "
+        let i = 0;
+        'outer: while (i < 3) {
+            let j = 0;
+            while (j < 3) {
+                if (j == 1) {
+                    i += 1;
+                    continue 'outer;
+                }
+                if (i == 2) { break 'outer; }
+                print i * 10 + j;
+                j += 1;
+            }
+        }
+        print i;
+        let value = 'search: loop {
+            loop { break 'search 5; }
+        };
+        print value;
+    ";
+    assert_eq!(run(source), "0\n10\n2\n5\n");
+}
+
+#[test]
+fn break_and_continue_outside_loop_are_parse_errors() {
+    assert_eq!(
+        run("break;"),
+        "[Line: 1] Error: at 'break', 'break' outside of loop\n"
+    );
+    assert_eq!(
+        run("fun f() { continue; }"),
+        "[Line: 1] Error: at 'continue', 'continue' outside of loop\n"
+    );
+}
+
+#[test]
+fn unknown_label_is_a_parse_error() {
+    let output = run("while (true) { break 'missing; }");
+    assert_eq!(
+        output,
+        "[Line: 1] Error: at 'break', use of undeclared label 'missing\n"
+    );
+}
+
+#[test]
+fn return_inside_loop_expression_leaves_function() {
+    let source = "
+        fun find(xs, target) {
+            let i = 0;
+            let found = loop {
+                if (i == 3) { return -1; }
+                if (xs[i] == target) { break i; }
+                i += 1;
+            };
+            return found;
+        }
+        print find([4, 5, 6], 5);
+        print find([4, 5, 6], 9);
+    ";
+    assert_eq!(run(source), "1\n-1\n");
+}
+
+#[test]
+fn return_inside_match_expression_block_leaves_function() {
+    let source = "
+        fun describe(n) {
+            let label = match n {
+                0 => \"zero\",
+                _ => { return \"other\"; },
+            };
+            return label;
+        }
+        print describe(0);
+        print describe(3);
+    ";
+    assert_eq!(run(source), "zero\nother\n");
+}
+
+#[test]
+fn return_inside_loop_expression_is_not_caught() {
+    let source = "
+        fun f() {
+            try {
+                let x = loop { return 1; };
+            } catch (e) {
+                return 2;
+            } finally {
+                print \"finally\";
+            }
+        }
+        print f();
+    ";
+    assert_eq!(run(source), "finally\n1\n");
+}
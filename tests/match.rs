@@ -0,0 +1,105 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn literal_or_and_range_patterns() {
+    let source = "
+        fun describe(x) {
+            return match x {
+                0 => \"zero\",
+                1 | 2 => \"small\",
+                3..=9 => \"digit\",
+                10..20 => \"teen\",
+                \"s\" => \"string\",
+                null => \"null\",
+                _ => \"other\",
+            };
+        }
+        print describe(0);
+        print describe(2);
+        print describe(9);
+        print describe(19);
+        print describe(20);
+        print describe(\"s\");
+        print describe(null);
+    ";
+    assert_eq!(
+        run(source),
+        "zero\nsmall\ndigit\nteen\nother\nstring\nnull\n"
+    );
+}
+
+#[test]
+fn tuple_and_list_patterns_bind() {
+    let source = "
+        match (1, 2) {
+            (0, y) => { print y; },
+            (x, 2) => { print x; },
+        }
+        print match [1, 2, 3] {
+            [] => \"empty\",
+            [a] => a,
+            [a, rest...] => rest,
+        };
+    ";
+    assert_eq!(run(source), "1\n[2, 3]\n");
+}
+
+#[test]
+fn guards_fall_through_to_later_arms() {
+    let source = "
+        fun sign(n) {
+            return match n {
+                x if x < 0 => \"negative\",
+                0 => \"zero\",
+                x if x > 100 => \"large\",
+                _ => \"positive\",
+            };
+        }
+        print sign(-5);
+        print sign(0);
+        print sign(500);
+        print sign(5);
+    ";
+    assert_eq!(run(source), "negative\nzero\nlarge\npositive\n");
+}
+
+#[test]
+fn non_exhaustive_match_is_a_pattern_mismatch() {
+    assert_eq!(
+        run("print match 5 { 1 => \"one\", 2 => \"two\" };"),
+        "Runtime Error: Pattern mismatch: no match arm for value '5'\n"
+    );
+}
+
+#[test]
+fn variant_patterns_bind_payloads() {
+    let source = "
+        enum Shape { Circle(r), Square(s), Empty }
+        fun area(shape) {
+            return match shape {
+                Shape.Circle(r) => 3 * r * r,
+                Shape.Square(s) => s * s,
+                Shape.Empty => 0,
+            };
+        }
+        print area(Shape.Circle(2));
+        print area(Shape.Square(3));
+        print area(Shape.Empty);
+    ";
+    assert_eq!(run(source), "12\n9\n0\n");
+}
+
+#[test]
+fn scrutinee_is_not_parsed_as_struct_literal() {
+    let source = "
+        struct Point { x }
+        let p = 1;
+        match p {}
+    ";
+    assert_eq!(
+        run(source),
+        "Runtime Error: Pattern mismatch: no match arm for value '1'\n"
+    );
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn int_arithmetic_overflow_is_an_error() {
+    assert_eq!(
+        run("print 9223372036854775807 + 1;"),
+        "Runtime Error: Integer overflow: 'Add' of 9223372036854775807 and 1 overflows an int\n"
+    );
+    assert_eq!(
+        run("print 9223372036854775807 * 2;"),
+        "Runtime Error: Integer overflow: 'Mul' of 9223372036854775807 and 2 overflows an int\n"
+    );
+}
+
+#[test]
+fn int_division_truncates() {
+    let source = "
+        print 7 / 2;
+        print -7 / 2;
+        print 10 / 5;
+        print 7.0 / 2;
+    ";
+    assert_eq!(run(source), "3\n-3\n2\n3.5\n");
+}
+
+#[test]
+fn modulo_keeps_sign_of_dividend() {
+    assert_eq!(run("print 7 % 3; print -7 % 3;"), "1\n-1\n");
+}
+
+#[test]
+fn mixed_arithmetic_promotes_to_float() {
+    let source = "
+        print 1 + 2.5;
+        print 2 * 1.5;
+        print 1.0;
+        print 3 > 2.5;
+    ";
+    assert_eq!(run(source), "3.5\n3.0\n1.0\ntrue\n");
+}
+
+#[test]
+fn int_and_float_compare_by_value() {
+    assert_eq!(run("print 1 == 1.0; print 1 != 1.0;"), "true\nfalse\n");
+}
+
+#[test]
+fn oversized_int_literal_is_rejected() {
+    assert_eq!(
+        run("print 9223372036854775808;"),
+        "[Line: 1] Error: at '9223372036854775808', integer literal is too large\n"
+    );
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn compound_assignment_updates_variables() {
+    let source = "
+        let x = 10;
+        x += 5;
+        print x;
+        x -= 3;
+        print x;
+        x *= 2;
+        print x;
+        x /= 4;
+        print x;
+        let s = \"a\";
+        s += \"b\";
+        print s;
+    ";
+    assert_eq!(run(source), "15\n12\n24\n6\nab\n");
+}
+
+#[test]
+fn compound_assignment_updates_fields() {
+    let source = "
+        struct Point { x, y }
+        let p = Point { x: 1, y: 8 };
+        p.x += 2;
+        p.y /= 2;
+        print p.x;
+        print p.y;
+    ";
+    assert_eq!(run(source), "3\n4\n");
+}
+
+#[test]
+fn compound_division_by_zero_is_an_error() {
+    assert_eq!(
+        run("let x = 1; x /= 0;"),
+        "Runtime Error: Division by zero: cannot divide by zero\n"
+    );
+}
+
+#[test]
+fn compound_assignment_updates_indexes() {
+    let source = "
+        let xs = [1, 2, 3];
+        xs[1] *= 10;
+        xs[2] -= 1;
+        print xs;
+    ";
+    assert_eq!(run(source), "[1, 20, 2]\n");
+}
+
+#[test]
+fn modulo_power_and_bitwise_operators() {
+    let source = "
+        print 7 % 4;
+        print 2 ** 10;
+        print 2 ** -1;
+        print 6 & 3;
+        print 6 | 3;
+        print 6 ^ 3;
+        print ~5;
+        print 1 << 4;
+        print 32 >> 2;
+    ";
+    assert_eq!(run(source), "3\n1024\n0.5\n2\n7\n5\n-6\n16\n8\n");
+}
+
+#[test]
+fn operator_precedence_is_pinned() {
+    let source = "
+        print -2 ** 2;
+        print 2 ** 3 ** 2;
+        print 6 & 3 * 2;
+        print 1 | 2 ^ 3;
+        print 1 << 2 + 1;
+    ";
+    assert_eq!(run(source), "4\n512\n4\n1\n5\n");
+}
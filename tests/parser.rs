@@ -0,0 +1,68 @@
+mod common;
+
+use std::process::Output;
+
+use common::{run_output, stdout};
+
+fn assert_reported(output: Output, error: &str) {
+    assert!(stdout(&output).starts_with(error));
+    // A panic would print its message to stderr
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn invalid_function_name_is_reported() {
+    assert_reported(run_output("fun 1() {}"), "[Line: 1] Error: at '1'");
+}
+
+#[test]
+fn invalid_parameter_name_is_reported() {
+    assert_reported(
+        run_output("fun f(1) {}"),
+        "[Line: 1] Error: at '1', Expected parameter name",
+    );
+}
+
+#[test]
+fn invalid_variable_name_is_reported() {
+    assert_reported(
+        run_output("let 1 = 2;"),
+        "[Line: 1] Error: at '1', Expected identifier",
+    );
+}
+
+#[test]
+fn invalid_constant_name_is_reported() {
+    assert_reported(
+        run_output("const 1 = 2;"),
+        "[Line: 1] Error: at '1', Expected identifier",
+    );
+}
+
+#[test]
+fn invalid_struct_member_names_are_reported() {
+    assert_reported(
+        run_output("struct 2 {}"),
+        "[Line: 1] Error: at '2', Expected struct name",
+    );
+    assert_reported(
+        run_output("struct S { fun 1() {} }"),
+        "[Line: 1] Error: at '1', Expected method name",
+    );
+    assert_reported(
+        run_output("struct S { 1 }"),
+        "[Line: 1] Error: at '1', Expected field name",
+    );
+}
+
+#[test]
+fn invalid_field_access_is_reported() {
+    assert_reported(
+        run_output("let p = 1; print p.2;"),
+        "[Line: 1] Error: at '2', Expected field name after '.'",
+    );
+    assert_reported(
+        run_output("struct S { a } let s = S { a: 1, 2: 3 };"),
+        "[Line: 1] Error: at '2', Expected field name",
+    );
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn ranges_display_with_bounds_and_step() {
+    let source = "
+        print 0..3;
+        print 0..=3;
+        print (1..10).step(3);
+    ";
+    assert_eq!(run(source), "0..3\n0..=3\n(1..10).step(3)\n");
+}
+
+#[test]
+fn for_in_iterates_ranges() {
+    let source = "
+        for i in 0..3 { print i; }
+        for i in 1..=2 { print i; }
+        for i in 3..=1 { print i; }
+        for i in (0..10).step(4) { print i; }
+        for i in (5..0).step(-2) { print i; }
+    ";
+    assert_eq!(run(source), "0\n1\n2\n1\n2\n0\n4\n8\n5\n3\n1\n");
+}
+
+#[test]
+fn zero_step_is_an_error() {
+    assert_eq!(
+        run("print (0..5).step(0);"),
+        "Runtime Error: Invalid argument: range step cannot be zero\n"
+    );
+}
+
+#[test]
+fn for_in_iterates_strings_lists_and_maps() {
+    let source = "
+        for c in \"héy\" { print c; }
+        for x in [1, \"a\"] { print x; }
+        for entry in {\"a\": 1, \"b\": 2} { print entry; }
+        for (a, b) in [(1, 2), (3, 4)] { print a + b; }
+    ";
+    assert_eq!(run(source), "h\né\ny\n1\na\n(\"a\", 1)\n(\"b\", 2)\n3\n7\n");
+}
+
+#[test]
+fn for_in_over_non_iterable_is_an_error() {
+    assert_eq!(
+        run("for x in 5 { }"),
+        "Runtime Error: Type mismatch: cannot iterate over type 'int'\n"
+    );
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::{repl, run};
+
+#[test]
+fn assignment_in_block_persists() {
+    let output = run("let x = 1; { x = 2; } print x;");
+    assert_eq!(output, "2\n");
+}
+
+#[test]
+fn assignment_in_nested_blocks_persists() {
+    let output = run("let x = 1; { { { x = 3; } } } print x;");
+    assert_eq!(output, "3\n");
+}
+
+#[test]
+fn assignment_in_if_branches_persists() {
+    let source = "
+        let x = 1;
+        if (true) { x = 2; } else { x = 0; }
+        print x;
+        if (false) { x = 0; } else { x = 3; }
+        print x;
+    ";
+    assert_eq!(run(source), "2\n3\n");
+}
+
+#[test]
+fn shadowed_variable_does_not_leak() {
+    let output = run("let x = 1; { let x = 2; x = 3; } print x;");
+    assert_eq!(output, "1\n");
+}
+
+#[test]
+fn closure_writes_to_captured_scope() {
+    let source = "
+        fun counter() {
+            let count = 0;
+            fun inc() { count = count + 1; return count; }
+            return inc;
+        }
+        let c = counter();
+        c();
+        print c();
+    ";
+    assert_eq!(run(source), "2\n");
+}
+
+#[test]
+fn undefined_and_uninitialized_reads_are_distinguished() {
+    let source = "
+        let x;
+        try { print x; } catch (e) { print e.kind; }
+        try { print y; } catch (e) { print e.kind; }
+    ";
+    assert_eq!(run(source), "UninitializedAccess\nUndefinedVariable\n");
+}
+
+#[test]
+fn const_cannot_be_redeclared_in_same_scope() {
+    let output = run("const x = 1; let x = 2; x = 3; print x;");
+    assert!(output.contains("cannot redeclare constant 'x'"));
+    assert!(!output.contains('3'));
+}
+
+#[test]
+fn const_can_be_shadowed_in_inner_scope() {
+    let source = "const x = 1; { let x = 2; x = 3; print x; } print x;";
+    assert_eq!(run(source), "3\n1\n");
+}
+
+#[test]
+fn const_redeclared_on_later_repl_line_is_rejected() {
+    let output = repl("const x = 1;\nlet x = 2;\nprint x;\n");
+
+    assert!(output.contains("Runtime Error: Constant reassignment: cannot redeclare constant 'x'"));
+    assert!(output.contains(">> 1\n"));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn interpolation_evaluates_expressions() {
+    let source = "
+        let name = \"world\";
+        let n = 2;
+        print \"hello {name}, {n + 1} times\";
+        print \"{n}{n * n}\";
+    ";
+    assert_eq!(run(source), "hello world, 3 times\n24\n");
+}
+
+#[test]
+fn interpolation_allows_nested_quotes() {
+    let source = "
+        let m = {\"k\": \"v\"};
+        print \"value: {m[\"k\"]}\";
+        print \"nested {\"inner {1 + 1}\"}\";
+    ";
+    assert_eq!(run(source), "value: v\nnested inner 2\n");
+}
+
+#[test]
+fn escaped_brace_is_literal() {
+    let source = "print \"braces \\{not} here\"; print \"closing } alone\";";
+    assert_eq!(run(source), "braces {not} here\nclosing } alone\n");
+}
+
+#[test]
+fn escape_sequences_in_strings() {
+    let source = r#"
+        print "a\tb";
+        print "line\nbreak";
+        print "quote \" and backslash \\";
+        print "cr[\r]" == "cr[" + "\u{d}" + "]";
+        print "nul[\0]" == "nul[" + "\u{0}" + "]";
+    "#;
+    assert_eq!(
+        run(source),
+        "a\tb\nline\nbreak\nquote \" and backslash \\\ntrue\ntrue\n"
+    );
+}
+
+#[test]
+fn escape_sequences_in_chars() {
+    let source = r#"
+        print '\n' == '\u{a}';
+        print '\'';
+        print "\u{1F600} \u{e9}";
+    "#;
+    assert_eq!(run(source), "true\n'\n\u{1F600} \u{e9}\n");
+}
+
+#[test]
+fn unknown_escape_is_an_error() {
+    assert_eq!(
+        run(r#"print "\q";"#),
+        "[Line: 1] Error: Unknown escape sequence '\\q'\n"
+    );
+    assert_eq!(
+        run(r#"print "\u{110000}";"#),
+        "[Line: 1] Error: Invalid unicode escape '\\u{110000}'\n"
+    );
+}
+
+#[test]
+fn escape_errors_report_source_line() {
+    let source = "let s = \"a\nb\\n\"; print \"\\z\";";
+    assert_eq!(
+        run(source),
+        "[Line: 2] Error: Unknown escape sequence '\\z'\n"
+    );
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use common::{repl, run, run_output, stdout};
+
+#[test]
+fn annotated_mismatch_is_rejected_before_running() {
+    let output = run_output("print 1; let x: int = \"a\";");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "[Line: 1] Error: Type Error: expected 'int' for 'x', found 'string'\n"
+    );
+}
+
+#[test]
+fn annotated_reassignment_mismatch_is_rejected() {
+    let output = run_output("let x: int = 1; x = \"a\";");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("expected 'int' for 'x', found 'string'"));
+}
+
+#[test]
+fn unannotated_cross_type_reassignment_is_accepted() {
+    let source = "
+        let best = null;
+        for x in [3, 1, 2] { best = x; }
+        print best * 2;
+        let s = \"a\";
+        s = 1;
+        print s - 1;
+        let l = 1;
+        l = [1];
+        print l == [1];
+    ";
+    let output = run_output(source);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "4\n0\ntrue\n");
+}
+
+#[test]
+fn reassignment_in_function_widens_outer_variable() {
+    let source = "
+        let x = \"a\";
+        fun get() { return x - 1; }
+        fun set() { x = 1; }
+        set();
+        print get();
+    ";
+    assert_eq!(run(source), "0\n");
+}
+
+#[test]
+fn unannotated_mismatch_without_reassignment_is_rejected() {
+    let output = run_output("let x = \"a\"; print x - 1;");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("'Sub' not supported for types 'string' and 'int'"));
+}
+
+#[test]
+fn parameter_annotations_are_checked_at_calls() {
+    let source = "
+        fun add(a: int, b: int) -> int { return a + b; }
+        print add(1, \"x\");
+    ";
+    let output = run_output(source);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("expected 'int' for argument 2, found 'string'"));
+}
+
+#[test]
+fn return_annotation_is_checked() {
+    let output = run_output("fun name() -> string { return 1; }");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("expected return type 'string', found 'int'"));
+}
+
+#[test]
+fn matching_annotations_run() {
+    let source = "
+        fun add(a: int, b: int) -> int { return a + b; }
+        let total: int = add(1, 2);
+        let ratio: float = 1.5;
+        print total;
+        print ratio;
+    ";
+    assert_eq!(run(source), "3\n1.5\n");
+}
+
+#[test]
+fn checker_state_is_kept_across_repl_lines() {
+    let output =
+        repl("let x: int = 1;\nx = \"a\";\nprint x;\nlet y = \"a\";\ny = 2;\nprint y - 1;\n");
+
+    assert!(output.contains("Type Error: expected 'int' for 'x', found 'string'"));
+    assert!(output.contains(">> 1\n"));
+    assert!(!output.contains("'Sub' not supported"));
+    assert!(output.ends_with(">> 1\n>> \n"));
+}
+
+#[test]
+fn reassignment_only_widens_the_binding_it_resolves_to() {
+    let source = "
+        fun f() { let n = \"a\"; n = 1; }
+        let n = \"b\";
+        let m: int = n;
+    ";
+    let output = run_output(source);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("expected 'int' for 'm', found 'string'"));
+}
+
+#[test]
+fn reassignment_on_earlier_repl_line_does_not_widen_later_bindings() {
+    let output = repl("fun f() { let x = 1; x = \"a\"; }\nlet x = \"s\";\nlet y: int = x;\n");
+    assert!(output.contains("expected 'int' for 'y', found 'string'"));
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn comments_and_strings_accept_non_ascii() {
+    let source = "
+        // commentaire: café ☕ 日本語
+        /* 多行
+           コメント */
+        print \"naïve ☕\"; // ünïcödé
+        print 'é';
+        print \"日本\" + \"語\";
+    ";
+    assert_eq!(run(source), "naïve ☕\né\n日本語\n");
+}
+
+#[test]
+fn identifiers_accept_xid_characters() {
+    let source = "
+        let café = 1;
+        let 名前 = \"日本\";
+        let Δx = 2;
+        print café + Δx;
+        print 名前;
+    ";
+    assert_eq!(run(source), "3\n日本\n");
+}
+
+#[test]
+fn non_identifier_symbol_is_rejected() {
+    assert_eq!(
+        run("let ☕ = 1;"),
+        "[Line: 1] Error: Unexpected symbol '☕'\n"
+    );
+}